@@ -0,0 +1,110 @@
+pub mod profile;
+pub mod render;
+
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Increases,
+    Decreases,
+    Plateaus,
+}
+
+impl Comparison {
+    pub fn new(text: &str) -> Result<Comparison, &'static str> {
+        match text {
+            "increases" => Ok(Comparison::Increases),
+            "decreases" => Ok(Comparison::Decreases),
+            "plateaus" => Ok(Comparison::Plateaus),
+            _ => Err("Unknown comparison mode"),
+        }
+    }
+
+    pub fn matches(&self, previous_sum: u64, current_sum: u64) -> bool {
+        match self {
+            Comparison::Increases => current_sum > previous_sum,
+            Comparison::Decreases => current_sum < previous_sum,
+            Comparison::Plateaus => current_sum == previous_sum,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Comparison::Increases => "increases",
+            Comparison::Decreases => "decreases",
+            Comparison::Plateaus => "plateaus",
+        }
+    }
+}
+
+/// Compares successive sliding window sums while only keeping the current
+/// window in memory, so inputs of any length can be streamed through it.
+///
+/// Every complete window takes part in the comparison: `n` depths yield
+/// `n - window_size + 1` window sums and one comparison less than that.
+pub struct SlidingWindowAnalyzer {
+    window_size: usize,
+    comparison: Comparison,
+    window: VecDeque<u32>,
+    window_sum: u64,
+    matches: usize,
+}
+
+impl SlidingWindowAnalyzer {
+    pub fn new(window_size: usize, comparison: Comparison) -> SlidingWindowAnalyzer {
+        assert!(window_size > 0);
+        SlidingWindowAnalyzer {
+            window_size,
+            comparison,
+            window: VecDeque::with_capacity(window_size),
+            window_sum: 0,
+            matches: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: u32) {
+        if self.window.len() < self.window_size {
+            self.window.push_back(depth);
+            self.window_sum += depth as u64;
+            return;
+        }
+
+        let oldest = self.window.pop_front().unwrap();
+        self.window.push_back(depth);
+        let previous_sum = self.window_sum;
+        self.window_sum = previous_sum - oldest as u64 + depth as u64;
+        if self.comparison.matches(previous_sum, self.window_sum) {
+            self.matches += 1;
+        }
+    }
+
+    pub fn matches(&self) -> usize {
+        self.matches
+    }
+}
+
+/// Counts how often the sum of a window compares to the sum of the window
+/// before it according to `comparison`.
+pub fn count_window_changes<I>(depths: I, window_size: usize, comparison: Comparison) -> usize
+where
+    I: IntoIterator<Item = u32>,
+{
+    let mut analyzer = SlidingWindowAnalyzer::new(window_size, comparison);
+    for depth in depths {
+        analyzer.push(depth);
+    }
+    analyzer.matches()
+}
+
+pub fn count_depth_increases(depths: &[u32], window_size: usize) -> usize {
+    count_window_changes(depths.iter().copied(), window_size, Comparison::Increases)
+}
+
+/// Sum of every complete window, indexed by the window's first depth.
+pub fn window_sums(depths: &[u32], window_size: usize) -> Vec<u64> {
+    assert!(window_size > 0);
+    depths
+        .windows(window_size)
+        .map(|window| window.iter().map(|&x| x as u64).sum())
+        .collect()
+}
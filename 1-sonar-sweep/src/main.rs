@@ -1,35 +1,141 @@
+use sonar_sweep::profile::{DepthProfile, ReportFormat};
+use sonar_sweep::render::{self, RenderFormat};
+use sonar_sweep::{Comparison, SlidingWindowAnalyzer};
 use std::env;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
+use std::process;
 
-const SLIDING_WINDOW_SIZE: usize = 3;
+const DEFAULT_SLIDING_WINDOW_SIZE: usize = 3;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Provide the depth input text file!");
+const USAGE: &str = "Usage: sonar-sweep [--window N] [--mode increases|decreases|plateaus] \
+                     [--report text|json | --render svg|ascii] [FILE|-]";
+
+struct Options {
+    input: Option<String>,
+    window_size: usize,
+    comparison: Comparison,
+    report: Option<ReportFormat>,
+    render: Option<RenderFormat>,
+}
+
+impl Options {
+    fn new(args: &[String]) -> Result<Options, String> {
+        let mut options = Options {
+            input: None,
+            window_size: DEFAULT_SLIDING_WINDOW_SIZE,
+            comparison: Comparison::Increases,
+            report: None,
+            render: None,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--window" => {
+                    let value = args.next().ok_or("Missing value for --window")?;
+                    options.window_size = match value.parse() {
+                        Ok(size) if size > 0 => size,
+                        _ => return Err(format!("Invalid window size: {}", value)),
+                    };
+                }
+                "--mode" => {
+                    let value = args.next().ok_or("Missing value for --mode")?;
+                    options.comparison = Comparison::new(value)?;
+                }
+                "--report" => {
+                    let value = args.next().ok_or("Missing value for --report")?;
+                    options.report = Some(ReportFormat::new(value)?);
+                }
+                "--render" => {
+                    let value = args.next().ok_or("Missing value for --render")?;
+                    options.render = Some(RenderFormat::new(value)?);
+                }
+                _ if options.input.is_none() => options.input = Some(arg.clone()),
+                _ => return Err(format!("Unexpected argument: {}", arg)),
+            }
+        }
+        if options.report.is_some() && options.render.is_some() {
+            return Err("--report and --render cannot be combined".to_string());
+        }
+        Ok(options)
     }
 
-    let file = File::open(&args[1]).expect("Error opening file");
-    let reader = BufReader::new(file);
-    let depths = reader
-        .lines()
-        .map(|x| x.unwrap().parse().expect("Error converting to int"))
-        .collect::<Vec<u32>>();
-
-    let mut depth_increases = 0;
-    let mut last_depth: Option<u32> = None;
-    for index in 0..depths.len() {
-        if index + SLIDING_WINDOW_SIZE >= depths.len() {
-            continue;
+    fn open_input(&self) -> io::Result<Box<dyn BufRead>> {
+        match self.input.as_deref() {
+            None | Some("-") => Ok(Box::new(BufReader::new(io::stdin()))),
+            Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
         }
-        let depth_sum: u32 = depths[index..index + SLIDING_WINDOW_SIZE].iter().sum();
-        if let Some(l_depth) = last_depth {
-            if depth_sum > l_depth {
-                depth_increases += 1;
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = Options::new(&args).unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(1);
+    });
+
+    let reader = options.open_input().expect("Error opening file");
+    let depths = read_depths(reader);
+
+    if let Some(format) = options.report {
+        // Percentiles need the whole series, so the report does not stream.
+        let depths: Vec<u32> = depths.collect();
+        let profile = match DepthProfile::new(&depths, options.window_size) {
+            Some(profile) => profile,
+            None => {
+                eprintln!("No depths to profile");
+                process::exit(1);
             }
+        };
+        match format {
+            ReportFormat::Text => println!("{}", profile),
+            ReportFormat::Json => println!("{}", profile.to_json()),
+        }
+        return;
+    }
+
+    if let Some(format) = options.render {
+        let depths: Vec<u32> = depths.collect();
+        match format {
+            RenderFormat::Svg => print!("{}", render::render_svg(&depths, options.window_size)),
+            RenderFormat::Ascii => print!(
+                "{}",
+                render::render_ascii(
+                    &depths,
+                    options.window_size,
+                    render::ASCII_WIDTH,
+                    render::ASCII_HEIGHT
+                )
+            ),
         }
-        last_depth = Some(depth_sum);
+        return;
     }
-    println!("Got {} depth increases", depth_increases);
+
+    let mut analyzer = SlidingWindowAnalyzer::new(options.window_size, options.comparison);
+    for depth in depths {
+        analyzer.push(depth);
+    }
+    println!(
+        "Got {} depth {}",
+        analyzer.matches(),
+        options.comparison.label()
+    );
+}
+
+fn read_depths(reader: Box<dyn BufRead>) -> impl Iterator<Item = u32> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(line_number, line)| {
+            let line = line.expect("Error reading line");
+            let line = line.trim();
+            if line.is_empty() {
+                return None;
+            }
+            Some(line.parse().unwrap_or_else(|_| {
+                panic!("Error converting line {} to int: {}", line_number + 1, line)
+            }))
+        })
 }
@@ -0,0 +1,259 @@
+use std::fmt;
+
+const PERCENTILES: [u32; 6] = [10, 25, 50, 75, 90, 99];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReportFormat {
+    Text,
+    Json,
+}
+
+impl ReportFormat {
+    pub fn new(text: &str) -> Result<ReportFormat, &'static str> {
+        match text {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err("Unknown report format"),
+        }
+    }
+}
+
+/// Monotonic stretch of depths, `start` and `end` are inclusive indices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
+    pub first_depth: u32,
+    pub last_depth: u32,
+}
+
+impl Run {
+    pub fn depth_count(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+/// Stretch of consecutive readings at the maximum depth, `start` and `end`
+/// are inclusive indices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trench {
+    pub start: usize,
+    pub end: usize,
+    pub depth: u32,
+}
+
+/// Difference between the window starting at `window_index` and the one
+/// starting right after it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowChange {
+    pub window_index: usize,
+    pub delta: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DepthProfile {
+    pub count: usize,
+    pub window_size: usize,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    pub percentiles: Vec<(u32, u32)>,
+    pub longest_ascent: Option<Run>,
+    pub longest_descent: Option<Run>,
+    pub deepest_trench: Trench,
+    pub steepest_change: Option<WindowChange>,
+}
+
+impl DepthProfile {
+    /// Builds the profile of a depth series, `None` if it is empty.
+    ///
+    /// Ascents and descents are strictly monotonic and need at least two
+    /// depths. The deepest trench is the first stretch of consecutive
+    /// readings at the maximum depth.
+    pub fn new(depths: &[u32], window_size: usize) -> Option<DepthProfile> {
+        assert!(window_size > 0);
+        if depths.is_empty() {
+            return None;
+        }
+
+        let min = *depths.iter().min().unwrap();
+        let max = *depths.iter().max().unwrap();
+        let mean = depths.iter().map(|&x| x as f64).sum::<f64>() / depths.len() as f64;
+
+        let mut sorted = depths.to_vec();
+        sorted.sort_unstable();
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&p| (p, nearest_rank(&sorted, p)))
+            .collect();
+
+        let trench_start = depths.iter().position(|&x| x == max).unwrap();
+        let trench_len = depths[trench_start..]
+            .iter()
+            .take_while(|&&x| x == max)
+            .count();
+
+        Some(DepthProfile {
+            count: depths.len(),
+            window_size,
+            min,
+            max,
+            mean,
+            percentiles,
+            longest_ascent: longest_run(depths, |a, b| b > a),
+            longest_descent: longest_run(depths, |a, b| b < a),
+            deepest_trench: Trench {
+                start: trench_start,
+                end: trench_start + trench_len - 1,
+                depth: max,
+            },
+            steepest_change: steepest_change(depths, window_size),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, value)| format!("\"p{}\":{}", p, value))
+            .collect::<Vec<String>>()
+            .join(",");
+        let steepest_change = match self.steepest_change {
+            Some(change) => format!(
+                "{{\"window_index\":{},\"delta\":{}}}",
+                change.window_index, change.delta
+            ),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"count\":{},\"window_size\":{},\"min\":{},\"max\":{},\"mean\":{:.3},\
+             \"percentiles\":{{{}}},\"longest_ascent\":{},\"longest_descent\":{},\
+             \"deepest_trench\":{{\"start\":{},\"end\":{},\"depth\":{}}},\
+             \"steepest_change\":{}}}",
+            self.count,
+            self.window_size,
+            self.min,
+            self.max,
+            self.mean,
+            percentiles,
+            run_json(self.longest_ascent),
+            run_json(self.longest_descent),
+            self.deepest_trench.start,
+            self.deepest_trench.end,
+            self.deepest_trench.depth,
+            steepest_change
+        )
+    }
+}
+
+impl fmt::Display for DepthProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Depths: {}", self.count)?;
+        writeln!(
+            f,
+            "Min/Max/Mean: {} / {} / {:.3}",
+            self.min, self.max, self.mean
+        )?;
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, value)| format!("p{}={}", p, value))
+            .collect::<Vec<String>>()
+            .join(" ");
+        writeln!(f, "Percentiles: {}", percentiles)?;
+        for (label, run) in [
+            ("Longest ascent", self.longest_ascent),
+            ("Longest descent", self.longest_descent),
+        ] {
+            match run {
+                Some(run) => writeln!(
+                    f,
+                    "{}: {} depths at {}..={} ({} -> {})",
+                    label,
+                    run.depth_count(),
+                    run.start,
+                    run.end,
+                    run.first_depth,
+                    run.last_depth
+                )?,
+                None => writeln!(f, "{}: none", label)?,
+            }
+        }
+        let trench = self.deepest_trench;
+        writeln!(
+            f,
+            "Deepest trench: {} at {}..={}",
+            trench.depth, trench.start, trench.end
+        )?;
+        match self.steepest_change {
+            Some(change) => write!(
+                f,
+                "Steepest change (window {}): {:+} from window {} to {}",
+                self.window_size,
+                change.delta,
+                change.window_index,
+                change.window_index + 1
+            ),
+            None => write!(f, "Steepest change (window {}): none", self.window_size),
+        }
+    }
+}
+
+fn nearest_rank(sorted: &[u32], percentile: u32) -> u32 {
+    let rank = (percentile as usize * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+fn longest_run<F>(depths: &[u32], continues: F) -> Option<Run>
+where
+    F: Fn(u32, u32) -> bool,
+{
+    let mut longest: Option<Run> = None;
+    let mut start = 0;
+    for index in 1..=depths.len() {
+        if index < depths.len() && continues(depths[index - 1], depths[index]) {
+            continue;
+        }
+        let run = Run {
+            start,
+            end: index - 1,
+            first_depth: depths[start],
+            last_depth: depths[index - 1],
+        };
+        if run.depth_count() > 1 && longest.is_none_or(|x| run.depth_count() > x.depth_count()) {
+            longest = Some(run);
+        }
+        start = index;
+    }
+    longest
+}
+
+fn steepest_change(depths: &[u32], window_size: usize) -> Option<WindowChange> {
+    // Neighbouring windows share all but one depth, so their difference is
+    // the depth entering minus the depth leaving.
+    let mut steepest: Option<WindowChange> = None;
+    for window_index in 0..depths.len().saturating_sub(window_size) {
+        let delta = depths[window_index + window_size] as i64 - depths[window_index] as i64;
+        if steepest.is_none_or(|x| delta.abs() > x.delta.abs()) {
+            steepest = Some(WindowChange {
+                window_index,
+                delta,
+            });
+        }
+    }
+    steepest
+}
+
+fn run_json(run: Option<Run>) -> String {
+    match run {
+        Some(run) => format!(
+            "{{\"start\":{},\"end\":{},\"length\":{},\"first_depth\":{},\"last_depth\":{}}}",
+            run.start,
+            run.end,
+            run.depth_count(),
+            run.first_depth,
+            run.last_depth
+        ),
+        None => "null".to_string(),
+    }
+}
@@ -0,0 +1,214 @@
+use crate::window_sums;
+use std::fmt::Write;
+
+const SVG_WIDTH: f64 = 960.0;
+const SVG_PANEL_HEIGHT: f64 = 240.0;
+const SVG_MARGIN: f64 = 40.0;
+
+pub const ASCII_WIDTH: usize = 72;
+pub const ASCII_HEIGHT: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderFormat {
+    Svg,
+    Ascii,
+}
+
+impl RenderFormat {
+    pub fn new(text: &str) -> Result<RenderFormat, &'static str> {
+        match text {
+            "svg" => Ok(RenderFormat::Svg),
+            "ascii" => Ok(RenderFormat::Ascii),
+            _ => Err("Unknown render format"),
+        }
+    }
+}
+
+/// Flags every window sum that is larger than the one before it.
+fn increases(sums: &[u64]) -> Vec<bool> {
+    let mut increased = vec![false; sums.len()];
+    for index in 1..sums.len() {
+        increased[index] = sums[index] > sums[index - 1];
+    }
+    increased
+}
+
+/// Plots the raw depths and the window sums as two stacked line charts.
+/// Depth grows downwards like on a sonar screen, and every window sum that
+/// increased is marked with a red dot.
+pub fn render_svg(depths: &[u32], window_size: usize) -> String {
+    let raw: Vec<u64> = depths.iter().map(|&x| x as u64).collect();
+    let sums = window_sums(depths, window_size);
+    let increased = increases(&sums);
+    let height = 2.0 * SVG_PANEL_HEIGHT + 3.0 * SVG_MARGIN;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\" font-family=\"monospace\" font-size=\"12\">",
+        SVG_WIDTH, height, SVG_WIDTH, height
+    )
+    .unwrap();
+    writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>").unwrap();
+    svg_panel(&mut svg, "Depth", &raw, &[], SVG_MARGIN, "steelblue");
+    svg_panel(
+        &mut svg,
+        &format!("Window sum ({})", window_size),
+        &sums,
+        &increased,
+        2.0 * SVG_MARGIN + SVG_PANEL_HEIGHT,
+        "darkgreen",
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn svg_panel(
+    svg: &mut String,
+    title: &str,
+    values: &[u64],
+    highlighted: &[bool],
+    top: f64,
+    color: &str,
+) {
+    let plot_width = SVG_WIDTH - 2.0 * SVG_MARGIN;
+    writeln!(
+        svg,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#ccc\"/>",
+        SVG_MARGIN, top, plot_width, SVG_PANEL_HEIGHT
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\">{}</text>",
+        SVG_MARGIN,
+        top - 8.0,
+        title
+    )
+    .unwrap();
+    if values.is_empty() {
+        return;
+    }
+
+    let min = *values.iter().min().unwrap();
+    let max = *values.iter().max().unwrap();
+    let x_step = if values.len() > 1 {
+        plot_width / (values.len() - 1) as f64
+    } else {
+        0.0
+    };
+    let range = (max - min).max(1) as f64;
+    let point = |index: usize| {
+        let x = SVG_MARGIN + index as f64 * x_step;
+        let y = top + (values[index] - min) as f64 / range * SVG_PANEL_HEIGHT;
+        (x, y)
+    };
+
+    writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}..{}</text>",
+        SVG_WIDTH - SVG_MARGIN,
+        top - 8.0,
+        min,
+        max
+    )
+    .unwrap();
+    svg.push_str("<polyline fill=\"none\" stroke=\"");
+    svg.push_str(color);
+    svg.push_str("\" stroke-width=\"1\" points=\"");
+    for index in 0..values.len() {
+        let (x, y) = point(index);
+        write!(svg, "{:.2},{:.2} ", x, y).unwrap();
+    }
+    svg.push_str("\"/>\n");
+
+    for (index, _) in highlighted.iter().enumerate().filter(|(_, &x)| x) {
+        let (x, y) = point(index);
+        writeln!(
+            svg,
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"2\" fill=\"crimson\"/>",
+            x, y
+        )
+        .unwrap();
+    }
+}
+
+/// Terminal fallback of [`render_svg`]. Values are bucketed into `width`
+/// columns by their mean; a window sum column is drawn with `+` when any
+/// sum in its bucket increased and with `*` otherwise.
+pub fn render_ascii(depths: &[u32], window_size: usize, width: usize, height: usize) -> String {
+    assert!(width > 0 && height > 1);
+    let raw: Vec<u64> = depths.iter().map(|&x| x as u64).collect();
+    let sums = window_sums(depths, window_size);
+    let increased = increases(&sums);
+
+    let mut chart = String::new();
+    ascii_panel(&mut chart, "Depth", &raw, &[], width, height);
+    chart.push('\n');
+    ascii_panel(
+        &mut chart,
+        &format!("Window sum ({})", window_size),
+        &sums,
+        &increased,
+        width,
+        height,
+    );
+    chart
+}
+
+fn ascii_panel(
+    chart: &mut String,
+    title: &str,
+    values: &[u64],
+    highlighted: &[bool],
+    width: usize,
+    height: usize,
+) {
+    if values.is_empty() {
+        writeln!(chart, "{}: no data", title).unwrap();
+        return;
+    }
+
+    let columns = width.min(values.len());
+    let buckets: Vec<(u64, bool)> = (0..columns)
+        .map(|column| {
+            let start = column * values.len() / columns;
+            let end = (column + 1) * values.len() / columns;
+            let mean = values[start..end].iter().sum::<u64>() / (end - start) as u64;
+            let marked = highlighted
+                .get(start..end)
+                .is_some_and(|x| x.contains(&true));
+            (mean, marked)
+        })
+        .collect();
+
+    let min = buckets.iter().map(|x| x.0).min().unwrap();
+    let max = buckets.iter().map(|x| x.0).max().unwrap();
+    let range = (max - min).max(1);
+    let mut rows = vec![vec![' '; columns]; height];
+    for (column, &(mean, marked)) in buckets.iter().enumerate() {
+        let row = ((mean - min) * (height as u64 - 1) / range) as usize;
+        rows[row][column] = if marked { '+' } else { '*' };
+    }
+
+    let label_width = max.to_string().len();
+    writeln!(chart, "{} ({} values)", title, values.len()).unwrap();
+    for (index, row) in rows.iter().enumerate() {
+        let label = if index == 0 {
+            min.to_string()
+        } else if index == height - 1 {
+            max.to_string()
+        } else {
+            String::new()
+        };
+        writeln!(
+            chart,
+            "{:>width$} |{}",
+            label,
+            row.iter().collect::<String>(),
+            width = label_width
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+use sonar_sweep::profile::{DepthProfile, Run, Trench, WindowChange};
+
+const EXAMPLE_DEPTHS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+#[test]
+fn example_profile() {
+    let profile = DepthProfile::new(&EXAMPLE_DEPTHS, 3).unwrap();
+    assert_eq!(profile.count, 10);
+    assert_eq!((profile.min, profile.max), (199, 269));
+    assert!((profile.mean - 225.6).abs() < 1e-9);
+    assert_eq!(
+        profile.percentiles,
+        vec![
+            (10, 199),
+            (25, 200),
+            (50, 208),
+            (75, 260),
+            (90, 263),
+            (99, 269)
+        ]
+    );
+    assert_eq!(
+        profile.longest_ascent,
+        Some(Run {
+            start: 0,
+            end: 3,
+            first_depth: 199,
+            last_depth: 210,
+        })
+    );
+    assert_eq!(profile.longest_ascent.unwrap().depth_count(), 4);
+    assert_eq!(
+        profile.longest_descent,
+        Some(Run {
+            start: 3,
+            end: 4,
+            first_depth: 210,
+            last_depth: 200,
+        })
+    );
+    assert_eq!(
+        profile.deepest_trench,
+        Trench {
+            start: 7,
+            end: 7,
+            depth: 269,
+        }
+    );
+    assert_eq!(
+        profile.steepest_change,
+        Some(WindowChange {
+            window_index: 4,
+            delta: 69,
+        })
+    );
+}
+
+#[test]
+fn ties_keep_the_first_occurrence() {
+    // Two ascents and two descents of equal length, two trenches at the
+    // same depth and two window changes of the same size.
+    let depths = [1, 2, 3, 1, 9, 9, 1, 9, 8, 7, 8, 9, 5, 4];
+    let profile = DepthProfile::new(&depths, 1).unwrap();
+    assert_eq!(
+        profile.longest_ascent.map(|x| (x.start, x.end)),
+        Some((0, 2))
+    );
+    assert_eq!(
+        profile.longest_descent.map(|x| (x.start, x.end)),
+        Some((7, 9))
+    );
+    assert_eq!(
+        profile.deepest_trench,
+        Trench {
+            start: 4,
+            end: 5,
+            depth: 9,
+        }
+    );
+    assert_eq!(
+        profile.steepest_change,
+        Some(WindowChange {
+            window_index: 3,
+            delta: 8,
+        })
+    );
+}
+
+#[test]
+fn single_depth() {
+    let profile = DepthProfile::new(&[5], 3).unwrap();
+    assert_eq!(
+        profile
+            .percentiles
+            .iter()
+            .map(|x| x.1)
+            .collect::<Vec<u32>>(),
+        vec![5; 6]
+    );
+    assert_eq!(profile.longest_ascent, None);
+    assert_eq!(profile.longest_descent, None);
+    assert_eq!(profile.steepest_change, None);
+    assert_eq!(
+        profile.to_json(),
+        "{\"count\":1,\"window_size\":3,\"min\":5,\"max\":5,\"mean\":5.000,\
+         \"percentiles\":{\"p10\":5,\"p25\":5,\"p50\":5,\"p75\":5,\"p90\":5,\"p99\":5},\
+         \"longest_ascent\":null,\"longest_descent\":null,\
+         \"deepest_trench\":{\"start\":0,\"end\":0,\"depth\":5},\"steepest_change\":null}"
+    );
+    assert!(profile.to_string().ends_with(
+        "Longest ascent: none\nLongest descent: none\nDeepest trench: 5 at 0..=0\n\
+         Steepest change (window 3): none"
+    ));
+    assert_eq!(DepthProfile::new(&[], 3), None);
+}
+
+#[test]
+fn example_json_and_text() {
+    let profile = DepthProfile::new(&EXAMPLE_DEPTHS, 3).unwrap();
+    assert_eq!(
+        profile.to_json(),
+        "{\"count\":10,\"window_size\":3,\"min\":199,\"max\":269,\"mean\":225.600,\
+         \"percentiles\":{\"p10\":199,\"p25\":200,\"p50\":208,\"p75\":260,\"p90\":263,\"p99\":269},\
+         \"longest_ascent\":{\"start\":0,\"end\":3,\"length\":4,\"first_depth\":199,\"last_depth\":210},\
+         \"longest_descent\":{\"start\":3,\"end\":4,\"length\":2,\"first_depth\":210,\"last_depth\":200},\
+         \"deepest_trench\":{\"start\":7,\"end\":7,\"depth\":269},\
+         \"steepest_change\":{\"window_index\":4,\"delta\":69}}"
+    );
+    assert_eq!(
+        profile.to_string(),
+        "Depths: 10\n\
+         Min/Max/Mean: 199 / 269 / 225.600\n\
+         Percentiles: p10=199 p25=200 p50=208 p75=260 p90=263 p99=269\n\
+         Longest ascent: 4 depths at 0..=3 (199 -> 210)\n\
+         Longest descent: 2 depths at 3..=4 (210 -> 200)\n\
+         Deepest trench: 269 at 7..=7\n\
+         Steepest change (window 3): +69 from window 4 to 5"
+    );
+}
+
+#[test]
+fn percentiles_use_the_nearest_rank() {
+    let depths: Vec<u32> = (1..=20).rev().collect();
+    let profile = DepthProfile::new(&depths, 2).unwrap();
+    assert_eq!(
+        profile.percentiles,
+        vec![(10, 2), (25, 5), (50, 10), (75, 15), (90, 18), (99, 20)]
+    );
+}
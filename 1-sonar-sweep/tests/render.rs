@@ -0,0 +1,57 @@
+use sonar_sweep::render::{render_ascii, render_svg};
+
+#[test]
+fn ascii_places_values_by_row_and_marks_increases() {
+    assert_eq!(
+        render_ascii(&[1, 2, 3, 2, 5], 1, 5, 3),
+        "Depth (5 values)\n\
+         1 |** * \n  |  *  \n5 |    *\n\
+         \n\
+         Window sum (1) (5 values)\n\
+         1 |*+ * \n  |  +  \n5 |    +\n"
+    );
+}
+
+#[test]
+fn ascii_buckets_values_by_their_mean() {
+    // Buckets [1, 3], [2, 6] and [4, 8] have the means 2, 4 and 6.
+    let chart = render_ascii(&[1, 3, 2, 6, 4, 8], 1, 3, 2);
+    assert!(chart.starts_with("Depth (6 values)\n2 |** \n6 |  *\n"));
+
+    // Only the first bucket holds a window sum without an increase.
+    let chart = render_ascii(&[5, 4, 3, 4, 5, 6], 1, 2, 2);
+    assert!(chart.ends_with("Window sum (1) (6 values)\n4 |* \n5 | +\n"));
+}
+
+#[test]
+fn too_few_depths_for_a_window() {
+    let chart = render_ascii(&[1, 5], 3, 5, 3);
+    assert!(chart.ends_with("\nWindow sum (3): no data\n"));
+
+    let svg = render_svg(&[1, 5], 3);
+    assert!(svg.contains(">Window sum (3)</text>"));
+    assert_eq!(svg.matches("<polyline").count(), 1);
+    assert_eq!(svg.matches("<circle").count(), 0);
+}
+
+#[test]
+fn single_value() {
+    assert_eq!(
+        render_ascii(&[7], 1, 5, 3),
+        "Depth (1 values)\n7 |*\n  | \n7 | \n\nWindow sum (1) (1 values)\n7 |*\n  | \n7 | \n"
+    );
+    let svg = render_svg(&[7], 1);
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn svg_marks_every_increased_window_sum() {
+    let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+    for (window_size, increases) in [(1, 7), (3, 5)] {
+        let svg = render_svg(&depths, window_size);
+        assert_eq!(svg.matches("<circle").count(), increases);
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains(&format!(">Window sum ({})</text>", window_size)));
+    }
+}
@@ -0,0 +1,102 @@
+use sonar_sweep::{count_depth_increases, count_window_changes, Comparison};
+
+const EXAMPLE_DEPTHS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+/// Straightforward reference: materialise every window sum, then compare
+/// neighbouring sums.
+fn naive_count(depths: &[u32], window_size: usize, comparison: Comparison) -> usize {
+    let sums: Vec<u64> = depths
+        .windows(window_size)
+        .map(|window| window.iter().map(|&x| x as u64).sum())
+        .collect();
+    sums.windows(2)
+        .filter(|pair| comparison.matches(pair[0], pair[1]))
+        .count()
+}
+
+/// Small xorshift generator so the differential test is reproducible
+/// without pulling in a dependency.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[test]
+fn example_single_depth_window() {
+    assert_eq!(count_depth_increases(&EXAMPLE_DEPTHS, 1), 7);
+}
+
+#[test]
+fn example_three_depth_window() {
+    assert_eq!(count_depth_increases(&EXAMPLE_DEPTHS, 3), 5);
+}
+
+#[test]
+fn final_window_is_compared() {
+    // Only the last window (1 + 1 + 2) is larger than the one before it.
+    assert_eq!(count_depth_increases(&[1, 1, 1, 2], 3), 1);
+    assert_eq!(count_depth_increases(&[5, 1, 1, 1, 9], 3), 1);
+}
+
+#[test]
+fn fewer_depths_than_window() {
+    assert_eq!(count_depth_increases(&[], 3), 0);
+    assert_eq!(count_depth_increases(&[1, 2], 3), 0);
+}
+
+#[test]
+fn exactly_one_window() {
+    assert_eq!(count_depth_increases(&[1, 2, 3], 3), 0);
+}
+
+#[test]
+fn equal_sums_are_plateaus() {
+    let depths = [3, 1, 2, 3, 1, 2];
+    assert_eq!(count_depth_increases(&depths, 3), 0);
+    assert_eq!(count_window_changes(depths, 3, Comparison::Decreases), 0);
+    assert_eq!(count_window_changes(depths, 3, Comparison::Plateaus), 3);
+}
+
+#[test]
+fn large_depths_do_not_overflow() {
+    let depths = [u32::MAX, u32::MAX, u32::MAX, u32::MAX - 1];
+    assert_eq!(count_window_changes(depths, 3, Comparison::Decreases), 1);
+}
+
+#[test]
+#[should_panic]
+fn zero_window_is_rejected() {
+    count_depth_increases(&EXAMPLE_DEPTHS, 0);
+}
+
+#[test]
+fn matches_naive_reference() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    let comparisons = [
+        Comparison::Increases,
+        Comparison::Decreases,
+        Comparison::Plateaus,
+    ];
+    for _ in 0..500 {
+        let len = (rng.next() % 40) as usize;
+        // A narrow value range makes equal window sums likely.
+        let depths: Vec<u32> = (0..len).map(|_| (rng.next() % 6) as u32).collect();
+        let window_size = 1 + (rng.next() % 8) as usize;
+        for comparison in comparisons {
+            assert_eq!(
+                count_window_changes(depths.iter().copied(), window_size, comparison),
+                naive_count(&depths, window_size, comparison),
+                "depths {:?}, window {}, {:?}",
+                depths,
+                window_size,
+                comparison
+            );
+        }
+    }
+}
@@ -0,0 +1,309 @@
+pub mod parser;
+pub mod planner;
+pub mod trajectory;
+
+use std::error::Error;
+use std::fmt;
+use trajectory::Trajectory;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Forward,
+    Down,
+    Up,
+    Left,
+    Right,
+    Turn,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NavigationCommand {
+    pub direction: Direction,
+    pub amount: u32,
+}
+
+impl Direction {
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Direction::Forward => "forward",
+            Direction::Down => "down",
+            Direction::Up => "up",
+            Direction::Left => "left",
+            Direction::Right => "right",
+            Direction::Turn => "turn",
+        }
+    }
+}
+
+impl fmt::Display for NavigationCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.direction.keyword(), self.amount)
+    }
+}
+
+/// Depth grows downwards, so a negative depth is above the surface and a
+/// negative aim points the submarine upwards. `lateral_pos` and `heading`
+/// (in degrees) are only changed by the heading model.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Position {
+    pub horizontal_pos: i64,
+    pub lateral_pos: i64,
+    pub depth: i64,
+    pub aim: i64,
+    pub heading: u32,
+}
+
+impl Position {
+    pub fn product(&self) -> Result<i64, NavigationError> {
+        self.horizontal_pos
+            .checked_mul(self.depth)
+            .ok_or(NavigationError::Overflow)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NavigationError {
+    Overflow,
+    AboveSurface { depth: i64 },
+    UnsupportedCommand(Direction),
+    InvalidHeading(u32),
+}
+
+impl fmt::Display for NavigationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NavigationError::Overflow => write!(f, "position exceeds the 64 bit range"),
+            NavigationError::AboveSurface { depth } => {
+                write!(
+                    f,
+                    "command would move the submarine above the surface to depth {}",
+                    depth
+                )
+            }
+            NavigationError::UnsupportedCommand(direction) => {
+                write!(
+                    f,
+                    "the model does not support '{}' commands",
+                    direction.keyword()
+                )
+            }
+            NavigationError::InvalidHeading(degrees) => {
+                write!(f, "{} degrees is not a multiple of 90", degrees)
+            }
+        }
+    }
+}
+
+impl Error for NavigationError {}
+
+/// Whether the submarine may rise above the surface (negative depth).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SurfacePolicy {
+    Allow,
+    Forbid,
+}
+
+/// Rule set deciding how a command changes the submarine's position.
+pub trait NavigationModel {
+    fn name(&self) -> &'static str;
+
+    /// Whether the model moves the submarine sideways, so the lateral
+    /// position is worth reporting.
+    fn is_three_dimensional(&self) -> bool {
+        false
+    }
+
+    fn apply(
+        &self,
+        position: &Position,
+        command: &NavigationCommand,
+    ) -> Result<Position, NavigationError>;
+}
+
+/// Part 1 rules: down and up change the depth directly.
+pub struct DirectModel;
+
+/// Part 2 rules: down and up change the aim, forward dives along it.
+pub struct AimModel;
+
+/// Aim rules in three dimensions: forward moves along the heading, which
+/// `left` and `right` change by the given degrees and `turn` sets. Heading
+/// 0 points along the horizontal axis and 90 along the lateral axis, only
+/// multiples of 90 degrees are supported.
+pub struct HeadingModel;
+
+fn checked_add(value: i64, amount: u32) -> Result<i64, NavigationError> {
+    value
+        .checked_add(amount as i64)
+        .ok_or(NavigationError::Overflow)
+}
+
+fn checked_sub(value: i64, amount: u32) -> Result<i64, NavigationError> {
+    value
+        .checked_sub(amount as i64)
+        .ok_or(NavigationError::Overflow)
+}
+
+impl NavigationModel for DirectModel {
+    fn name(&self) -> &'static str {
+        "direct"
+    }
+
+    fn apply(
+        &self,
+        position: &Position,
+        command: &NavigationCommand,
+    ) -> Result<Position, NavigationError> {
+        let mut next = *position;
+        match command.direction {
+            Direction::Down => next.depth = checked_add(position.depth, command.amount)?,
+            Direction::Forward => {
+                next.horizontal_pos = checked_add(position.horizontal_pos, command.amount)?
+            }
+            Direction::Up => next.depth = checked_sub(position.depth, command.amount)?,
+            direction => return Err(NavigationError::UnsupportedCommand(direction)),
+        };
+        Ok(next)
+    }
+}
+
+impl NavigationModel for AimModel {
+    fn name(&self) -> &'static str {
+        "aim"
+    }
+
+    fn apply(
+        &self,
+        position: &Position,
+        command: &NavigationCommand,
+    ) -> Result<Position, NavigationError> {
+        let mut next = *position;
+        match command.direction {
+            Direction::Down => next.aim = checked_add(position.aim, command.amount)?,
+            Direction::Forward => {
+                next.horizontal_pos = checked_add(position.horizontal_pos, command.amount)?;
+                next.depth = position
+                    .aim
+                    .checked_mul(command.amount as i64)
+                    .and_then(|x| x.checked_add(position.depth))
+                    .ok_or(NavigationError::Overflow)?;
+            }
+            Direction::Up => next.aim = checked_sub(position.aim, command.amount)?,
+            direction => return Err(NavigationError::UnsupportedCommand(direction)),
+        };
+        Ok(next)
+    }
+}
+
+fn quarter_turns(degrees: u32) -> Result<u32, NavigationError> {
+    if !degrees.is_multiple_of(90) {
+        return Err(NavigationError::InvalidHeading(degrees));
+    }
+    Ok(degrees / 90 % 4)
+}
+
+impl NavigationModel for HeadingModel {
+    fn name(&self) -> &'static str {
+        "heading"
+    }
+
+    fn is_three_dimensional(&self) -> bool {
+        true
+    }
+
+    fn apply(
+        &self,
+        position: &Position,
+        command: &NavigationCommand,
+    ) -> Result<Position, NavigationError> {
+        let mut next = *position;
+        let heading = position.heading / 90;
+        match command.direction {
+            Direction::Down | Direction::Up => return AimModel.apply(position, command),
+            Direction::Forward => {
+                let (axis, offset) = match heading {
+                    0 => (&mut next.horizontal_pos, command.amount as i64),
+                    1 => (&mut next.lateral_pos, command.amount as i64),
+                    2 => (&mut next.horizontal_pos, -(command.amount as i64)),
+                    _ => (&mut next.lateral_pos, -(command.amount as i64)),
+                };
+                *axis = axis.checked_add(offset).ok_or(NavigationError::Overflow)?;
+                next.depth = position
+                    .aim
+                    .checked_mul(command.amount as i64)
+                    .and_then(|x| x.checked_add(position.depth))
+                    .ok_or(NavigationError::Overflow)?;
+            }
+            Direction::Right => {
+                next.heading = (heading + quarter_turns(command.amount)?) % 4 * 90;
+            }
+            Direction::Left => {
+                next.heading = (heading + 4 - quarter_turns(command.amount)?) % 4 * 90;
+            }
+            Direction::Turn => next.heading = quarter_turns(command.amount)? * 90,
+        };
+        Ok(next)
+    }
+}
+
+pub fn navigation_models(name: &str) -> Result<Vec<Box<dyn NavigationModel>>, &'static str> {
+    match name {
+        "direct" => Ok(vec![Box::new(DirectModel)]),
+        "aim" => Ok(vec![Box::new(AimModel)]),
+        "heading" => Ok(vec![Box::new(HeadingModel)]),
+        "all" => Ok(vec![
+            Box::new(DirectModel),
+            Box::new(AimModel),
+            Box::new(HeadingModel),
+        ]),
+        _ => Err("Unknown navigation model"),
+    }
+}
+
+pub struct Submarine {
+    position: Position,
+    model: Box<dyn NavigationModel>,
+    surface_policy: SurfacePolicy,
+    trajectory: Option<Trajectory>,
+}
+
+impl Submarine {
+    pub fn new(model: Box<dyn NavigationModel>, surface_policy: SurfacePolicy) -> Submarine {
+        Submarine {
+            position: Position::default(),
+            model,
+            surface_policy,
+            trajectory: None,
+        }
+    }
+
+    /// Starts recording a sample of the position after every command.
+    pub fn record_trajectory(&mut self) {
+        self.trajectory = Some(Trajectory::new(&self.position));
+    }
+
+    pub fn trajectory(&self) -> Option<&Trajectory> {
+        self.trajectory.as_ref()
+    }
+
+    pub fn position(&self) -> &Position {
+        &self.position
+    }
+
+    pub fn model(&self) -> &dyn NavigationModel {
+        self.model.as_ref()
+    }
+
+    /// Executes a command, leaving the position untouched if it fails.
+    pub fn execute_command(&mut self, command: &NavigationCommand) -> Result<(), NavigationError> {
+        let next = self.model.apply(&self.position, command)?;
+        if self.surface_policy == SurfacePolicy::Forbid && next.depth < 0 {
+            return Err(NavigationError::AboveSurface { depth: next.depth });
+        }
+        self.position = next;
+        if let Some(trajectory) = &mut self.trajectory {
+            trajectory.record(&self.position);
+        }
+        Ok(())
+    }
+}
@@ -1,77 +1,147 @@
 use std::env;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::process;
+use sub_navigation::parser::parse_script;
+use sub_navigation::planner;
+use sub_navigation::trajectory::TrajectoryFormat;
+use sub_navigation::{navigation_models, Submarine, SurfacePolicy};
 
-enum Direction {
-    Forward,
-    Down,
-    Up,
-}
-struct NavigationCommand {
-    direction: Direction,
-    amount: u32,
-}
+const USAGE: &str = "Usage: sub-navigation [--model direct|aim|heading|all] [--forbid-surfacing] \
+                     [--trajectory csv|json|svg] FILE\n       \
+                     sub-navigation --plan HORIZONTAL DEPTH";
 
-#[derive(Default)]
-struct Submarine {
-    horizontal_pos: u32,
-    depth: u32,
-    aim: u32,
+struct Options {
+    model_name: String,
+    surface_policy: SurfacePolicy,
+    trajectory: Option<TrajectoryFormat>,
+    path: String,
 }
 
-impl NavigationCommand {
-    fn new(input_line: String) -> Result<NavigationCommand, &'static str> {
-        let components: Vec<&str> = input_line.split_whitespace().collect();
-        if components.len() != 2 {
-            return Err("Bad command");
-        }
-        let direction = match components[0] {
-            "down" => Direction::Down,
-            "forward" => Direction::Forward,
-            "up" => Direction::Up,
-            _ => {
-                panic!("Unknown direction");
+impl Options {
+    fn new(args: &[String]) -> Result<Options, String> {
+        let mut model_name = "aim".to_string();
+        let mut surface_policy = SurfacePolicy::Allow;
+        let mut trajectory = None;
+        let mut path = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--model" => {
+                    model_name = args.next().ok_or("Missing value for --model")?.clone();
+                }
+                "--forbid-surfacing" => surface_policy = SurfacePolicy::Forbid,
+                "--trajectory" => {
+                    let value = args.next().ok_or("Missing value for --trajectory")?;
+                    trajectory = Some(TrajectoryFormat::new(value)?);
+                }
+                _ if path.is_none() => path = Some(arg.clone()),
+                _ => return Err(format!("Unexpected argument: {}", arg)),
             }
-        };
-        let amount: u32 = components[1].parse().unwrap_or(0);
-        Ok(NavigationCommand { direction, amount })
+        }
+        if trajectory.is_some() && model_name == "all" {
+            return Err("--trajectory needs a single navigation model".to_string());
+        }
+        Ok(Options {
+            model_name,
+            surface_policy,
+            trajectory,
+            path: path.ok_or("Provide the command input text file!")?,
+        })
     }
 }
 
-impl Submarine {
-    fn execute_command(&mut self, command: &NavigationCommand) {
-        match command.direction {
-            Direction::Down => self.aim += command.amount,
-            Direction::Forward => {
-                self.horizontal_pos += command.amount;
-                self.depth += self.aim * command.amount;
-            }
-            Direction::Up => self.aim -= command.amount,
-        };
+/// Prints a command file reaching the target under the aim rules.
+fn print_plan(horizontal: &str, depth: &str) -> Result<(), String> {
+    let horizontal = horizontal
+        .parse()
+        .map_err(|_| format!("Invalid horizontal position: {}", horizontal))?;
+    let depth = depth
+        .parse()
+        .map_err(|_| format!("Invalid depth: {}", depth))?;
+    let commands = planner::plan(horizontal, depth).map_err(|err| err.to_string())?;
+    for command in commands {
+        println!("{}", command);
     }
+    Ok(())
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Provide the command input text file!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|x| x == "--plan") {
+        let result = match args.as_slice() {
+            [_, horizontal, depth] => print_plan(horizontal, depth),
+            _ => Err(USAGE.to_string()),
+        };
+        if let Err(err) = result {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+        return;
     }
+    let options = Options::new(&args).unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(1);
+    });
+    let models = navigation_models(&options.model_name).unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(1);
+    });
 
-    let file = File::open(&args[1]).expect("Error opening file");
-    let reader = BufReader::new(file);
-    let commands = reader
-        .lines()
-        .map(|x| NavigationCommand::new(x.unwrap_or_default()).expect("Error parsing command"))
-        .collect::<Vec<NavigationCommand>>();
+    let text = fs::read_to_string(&options.path).expect("Error reading file");
+    let commands = parse_script(&text).unwrap_or_else(|errors| {
+        for err in &errors {
+            eprintln!("{}:{}", options.path, err);
+        }
+        eprintln!("{} bad command(s)", errors.len());
+        process::exit(1);
+    });
 
-    let mut submarine = Submarine::default();
-    for command in &commands {
-        submarine.execute_command(command);
+    let mut failed = false;
+    'models: for model in models {
+        let mut submarine = Submarine::new(model, options.surface_policy);
+        if options.trajectory.is_some() {
+            submarine.record_trajectory();
+        }
+        let model_name = submarine.model().name();
+        for source in &commands {
+            if let Err(err) = submarine.execute_command(&source.command) {
+                eprintln!(
+                    "{}:{}: {} model: {}",
+                    options.path, source.line, model_name, err
+                );
+                // Keep replaying under the remaining models for comparison.
+                failed = true;
+                continue 'models;
+            }
+        }
+
+        if let (Some(format), Some(trajectory)) = (options.trajectory, submarine.trajectory()) {
+            print!("{}", trajectory.export(format));
+            continue;
+        }
+        let position = submarine.position();
+        let product = position
+            .product()
+            .map_or_else(|err| err.to_string(), |x| x.to_string());
+        if submarine.model().is_three_dimensional() {
+            println!(
+                "Submarine final position ({} model) is {};{};{} heading {}: {}",
+                model_name,
+                position.horizontal_pos,
+                position.lateral_pos,
+                position.depth,
+                position.heading,
+                product
+            );
+        } else {
+            println!(
+                "Submarine final position ({} model) is {};{}: {}",
+                model_name, position.horizontal_pos, position.depth, product
+            );
+        }
+    }
+    if failed {
+        process::exit(1);
     }
-    println!(
-        "Submarine final position is {};{}: {}",
-        submarine.horizontal_pos,
-        submarine.depth,
-        submarine.horizontal_pos * submarine.depth
-    );
 }
@@ -0,0 +1,436 @@
+use crate::{Direction, NavigationCommand};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Upper bound for the number of commands `repeat` blocks and macros may
+/// expand into.
+pub const MAX_EXPANDED_COMMANDS: usize = 10_000_000;
+
+const KEYWORDS: [&str; 8] = [
+    "forward", "down", "up", "left", "right", "turn", "macro", "repeat",
+];
+
+#[derive(Debug, PartialEq)]
+pub enum ParseErrorReason {
+    MissingDirection,
+    MissingAmount,
+    UnknownDirection(String),
+    InvalidAmount(String),
+    UnexpectedToken(String),
+    InvalidMacroName(String),
+    DuplicateMacro(String),
+    UnknownMacro(String),
+    NestedMacro,
+    InvalidRepeatCount(String),
+    MissingOpeningBrace,
+    UnmatchedClosingBrace,
+    UnclosedBlock,
+    ExpansionLimit,
+}
+
+/// Parse error pointing at a 1-based line and column of the input.
+#[derive(Debug, PartialEq)]
+pub struct NavigationParseError {
+    pub line: usize,
+    pub column: usize,
+    pub reason: ParseErrorReason,
+}
+
+impl fmt::Display for ParseErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorReason::MissingDirection => write!(f, "missing direction"),
+            ParseErrorReason::MissingAmount => write!(f, "missing amount"),
+            ParseErrorReason::UnknownDirection(token) => {
+                write!(f, "unknown direction '{}'", token)
+            }
+            ParseErrorReason::InvalidAmount(token) => {
+                write!(
+                    f,
+                    "invalid amount '{}', expected an unsigned integer",
+                    token
+                )
+            }
+            ParseErrorReason::UnexpectedToken(token) => write!(f, "unexpected '{}'", token),
+            ParseErrorReason::InvalidMacroName(token) => {
+                write!(f, "'{}' is not a valid macro name", token)
+            }
+            ParseErrorReason::DuplicateMacro(name) => {
+                write!(f, "macro '{}' is already defined", name)
+            }
+            ParseErrorReason::UnknownMacro(name) => write!(f, "unknown macro '{}'", name),
+            ParseErrorReason::NestedMacro => {
+                write!(f, "macros can only be defined at the top level")
+            }
+            ParseErrorReason::InvalidRepeatCount(token) => {
+                write!(f, "invalid repeat count '{}'", token)
+            }
+            ParseErrorReason::MissingOpeningBrace => write!(f, "expected '{{'"),
+            ParseErrorReason::UnmatchedClosingBrace => write!(f, "unmatched '}}'"),
+            ParseErrorReason::UnclosedBlock => write!(f, "block is never closed"),
+            ParseErrorReason::ExpansionLimit => {
+                write!(f, "expands to more than {} commands", MAX_EXPANDED_COMMANDS)
+            }
+        }
+    }
+}
+
+impl fmt::Display for NavigationParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.reason)
+    }
+}
+
+impl Error for NavigationParseError {}
+
+/// Splits a line on whitespace, keeping the 1-based column of every token.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (index, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(index),
+            (Some(token_start), true) => {
+                let column = line[..token_start].chars().count() + 1;
+                tokens.push((column, &line[token_start..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+impl NavigationCommand {
+    /// Parses a single `<direction> <amount>` line, `line` is only used to
+    /// locate errors.
+    pub fn new(input_line: &str, line: usize) -> Result<NavigationCommand, NavigationParseError> {
+        let error = |column, reason| NavigationParseError {
+            line,
+            column,
+            reason,
+        };
+        let components = tokens(input_line);
+        let (direction_column, direction) = *components
+            .first()
+            .ok_or_else(|| error(1, ParseErrorReason::MissingDirection))?;
+        let direction = match direction {
+            "down" => Direction::Down,
+            "forward" => Direction::Forward,
+            "up" => Direction::Up,
+            "left" => Direction::Left,
+            "right" => Direction::Right,
+            "turn" => Direction::Turn,
+            _ => {
+                return Err(error(
+                    direction_column,
+                    ParseErrorReason::UnknownDirection(direction.to_string()),
+                ))
+            }
+        };
+
+        let (amount_column, amount) = *components.get(1).ok_or_else(|| {
+            error(
+                input_line.trim_end().chars().count() + 1,
+                ParseErrorReason::MissingAmount,
+            )
+        })?;
+        let amount = amount.parse().map_err(|_| {
+            error(
+                amount_column,
+                ParseErrorReason::InvalidAmount(amount.to_string()),
+            )
+        })?;
+
+        if let Some(&(column, token)) = components.get(2) {
+            return Err(error(
+                column,
+                ParseErrorReason::UnexpectedToken(token.to_string()),
+            ));
+        }
+        Ok(NavigationCommand { direction, amount })
+    }
+}
+
+/// Command together with the source line it was written on. Commands
+/// expanded from a macro point at the line inside the macro body.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceCommand {
+    pub line: usize,
+    pub command: NavigationCommand,
+}
+
+#[derive(Debug)]
+enum Statement {
+    Command(SourceCommand),
+    Repeat {
+        line: usize,
+        column: usize,
+        count: u32,
+        body: Vec<Statement>,
+    },
+    Call {
+        line: usize,
+        column: usize,
+        name: String,
+    },
+}
+
+enum BlockKind {
+    Root,
+    Macro(String),
+    Repeat(u32),
+    /// Block whose header was invalid, parsed only to keep braces balanced.
+    Invalid,
+}
+
+struct Block {
+    kind: BlockKind,
+    line: usize,
+    column: usize,
+    statements: Vec<Statement>,
+}
+
+fn is_macro_name(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !KEYWORDS.contains(&token)
+}
+
+/// Parses a command file and expands it into the commands to execute.
+///
+/// Besides `<direction> <amount>` lines the grammar knows `#` comments,
+/// `macro <name> { ... }` definitions at the top level, `<name>` lines
+/// invoking a previously defined macro and nestable `repeat <n> { ... }`
+/// blocks. Blank lines are skipped and every bad line is reported instead
+/// of stopping at the first one.
+pub fn parse_script(text: &str) -> Result<Vec<SourceCommand>, Vec<NavigationParseError>> {
+    let mut errors = Vec::new();
+    let mut macros: HashMap<String, Vec<Statement>> = HashMap::new();
+    let mut macro_sizes: HashMap<String, u64> = HashMap::new();
+    let mut blocks = vec![Block {
+        kind: BlockKind::Root,
+        line: 0,
+        column: 0,
+        statements: Vec::new(),
+    }];
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = index + 1;
+        let content = raw_line.split('#').next().unwrap();
+        let components = tokens(content);
+        let error = |column, reason| NavigationParseError {
+            line,
+            column,
+            reason,
+        };
+        let Some(&(column, first)) = components.first() else {
+            continue;
+        };
+
+        match first {
+            "}" => {
+                if let Some(&(column, token)) = components.get(1) {
+                    errors.push(error(
+                        column,
+                        ParseErrorReason::UnexpectedToken(token.to_string()),
+                    ));
+                }
+                if blocks.len() == 1 {
+                    errors.push(error(column, ParseErrorReason::UnmatchedClosingBrace));
+                    continue;
+                }
+                let block = blocks.pop().unwrap();
+                match block.kind {
+                    BlockKind::Macro(name) => {
+                        macro_sizes
+                            .insert(name.clone(), expanded_size(&block.statements, &macro_sizes));
+                        macros.insert(name, block.statements);
+                    }
+                    BlockKind::Repeat(count) => {
+                        blocks
+                            .last_mut()
+                            .unwrap()
+                            .statements
+                            .push(Statement::Repeat {
+                                line: block.line,
+                                column: block.column,
+                                count,
+                                body: block.statements,
+                            })
+                    }
+                    BlockKind::Root | BlockKind::Invalid => {}
+                }
+            }
+            "macro" | "repeat" => {
+                let opens_block = components.last().is_some_and(|x| x.1 == "{");
+                let kind = block_header(&components, blocks.len(), &macros)
+                    .map_err(|(column, reason)| errors.push(error(column, reason)))
+                    .unwrap_or(BlockKind::Invalid);
+                if opens_block {
+                    blocks.push(Block {
+                        kind,
+                        line,
+                        column,
+                        statements: Vec::new(),
+                    });
+                }
+            }
+            _ if components.len() == 1 && is_macro_name(first) => {
+                if macros.contains_key(first) {
+                    blocks.last_mut().unwrap().statements.push(Statement::Call {
+                        line,
+                        column,
+                        name: first.to_string(),
+                    });
+                } else {
+                    errors.push(error(
+                        column,
+                        ParseErrorReason::UnknownMacro(first.to_string()),
+                    ));
+                }
+            }
+            _ => match NavigationCommand::new(content, line) {
+                Ok(command) => blocks
+                    .last_mut()
+                    .unwrap()
+                    .statements
+                    .push(Statement::Command(SourceCommand { line, command })),
+                Err(err) => errors.push(err),
+            },
+        }
+    }
+
+    while blocks.len() > 1 {
+        let block = blocks.pop().unwrap();
+        errors.push(NavigationParseError {
+            line: block.line,
+            column: block.column,
+            reason: ParseErrorReason::UnclosedBlock,
+        });
+    }
+    if !errors.is_empty() {
+        errors.sort_by_key(|x| (x.line, x.column));
+        return Err(errors);
+    }
+
+    let mut commands = Vec::new();
+    expand(&blocks[0].statements, &macros, &macro_sizes, &mut commands).map_err(|err| vec![err])?;
+    Ok(commands)
+}
+
+/// Validates a `macro <name> {` or `repeat <n> {` line.
+fn block_header(
+    components: &[(usize, &str)],
+    depth: usize,
+    macros: &HashMap<String, Vec<Statement>>,
+) -> Result<BlockKind, (usize, ParseErrorReason)> {
+    let (keyword_column, keyword) = components[0];
+    let end_column = components.last().unwrap().0 + 1;
+    let (argument_column, argument) = *components
+        .get(1)
+        .ok_or((end_column, ParseErrorReason::MissingOpeningBrace))?;
+    match components.get(2) {
+        Some(&(_, "{")) => {}
+        Some(&(column, _)) => return Err((column, ParseErrorReason::MissingOpeningBrace)),
+        None => return Err((end_column, ParseErrorReason::MissingOpeningBrace)),
+    }
+    if let Some(&(column, token)) = components.get(3) {
+        return Err((column, ParseErrorReason::UnexpectedToken(token.to_string())));
+    }
+
+    if keyword == "repeat" {
+        return argument.parse().map(BlockKind::Repeat).map_err(|_| {
+            (
+                argument_column,
+                ParseErrorReason::InvalidRepeatCount(argument.to_string()),
+            )
+        });
+    }
+    if depth > 1 {
+        return Err((keyword_column, ParseErrorReason::NestedMacro));
+    }
+    if !is_macro_name(argument) {
+        return Err((
+            argument_column,
+            ParseErrorReason::InvalidMacroName(argument.to_string()),
+        ));
+    }
+    if macros.contains_key(argument) {
+        return Err((
+            argument_column,
+            ParseErrorReason::DuplicateMacro(argument.to_string()),
+        ));
+    }
+    Ok(BlockKind::Macro(argument.to_string()))
+}
+
+/// Number of commands the statements expand into, saturating instead of
+/// overflowing. `macro_sizes` holds the sizes of all macros defined so far.
+fn expanded_size(statements: &[Statement], macro_sizes: &HashMap<String, u64>) -> u64 {
+    statements.iter().fold(0, |total: u64, statement| {
+        total.saturating_add(statement_size(statement, macro_sizes))
+    })
+}
+
+fn statement_size(statement: &Statement, macro_sizes: &HashMap<String, u64>) -> u64 {
+    match statement {
+        Statement::Command(_) => 1,
+        Statement::Repeat { count, body, .. } => {
+            (*count as u64).saturating_mul(expanded_size(body, macro_sizes))
+        }
+        Statement::Call { name, .. } => macro_sizes[name],
+    }
+}
+
+/// Macros must be defined before they are used, so expansion always
+/// terminates. Every statement's size is checked against the limit before
+/// it is expanded, and statements expanding to nothing are skipped, so huge
+/// repeat counts never loop or allocate.
+fn expand(
+    statements: &[Statement],
+    macros: &HashMap<String, Vec<Statement>>,
+    macro_sizes: &HashMap<String, u64>,
+    commands: &mut Vec<SourceCommand>,
+) -> Result<(), NavigationParseError> {
+    for statement in statements {
+        let size = statement_size(statement, macro_sizes);
+        if size == 0 {
+            continue;
+        }
+        if (commands.len() as u64).saturating_add(size) > MAX_EXPANDED_COMMANDS as u64 {
+            let (line, column) = match statement {
+                Statement::Command(command) => (command.line, 1),
+                Statement::Repeat { line, column, .. } | Statement::Call { line, column, .. } => {
+                    (*line, *column)
+                }
+            };
+            return Err(NavigationParseError {
+                line,
+                column,
+                reason: ParseErrorReason::ExpansionLimit,
+            });
+        }
+        match statement {
+            Statement::Command(command) => commands.push(*command),
+            Statement::Repeat { count, body, .. } => {
+                for _ in 0..*count {
+                    expand(body, macros, macro_sizes, commands)?;
+                }
+            }
+            Statement::Call { name, .. } => {
+                expand(&macros[name], macros, macro_sizes, commands)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Like [`parse_script`], dropping the source lines.
+pub fn parse_commands(text: &str) -> Result<Vec<NavigationCommand>, Vec<NavigationParseError>> {
+    parse_script(text).map(|commands| commands.into_iter().map(|x| x.command).collect())
+}
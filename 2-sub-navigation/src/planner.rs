@@ -0,0 +1,237 @@
+use crate::{AimModel, Direction, NavigationCommand, Position, Submarine, SurfacePolicy};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum PlanError {
+    /// No command list under the aim rules ends at the target.
+    Unreachable,
+    /// Every plan without split amounts needs an amount that does not fit
+    /// into a command.
+    AmountOutOfRange,
+    /// Replaying the plan did not end at the target.
+    VerificationFailed(Position),
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlanError::Unreachable => write!(f, "target is unreachable"),
+            PlanError::AmountOutOfRange => {
+                write!(
+                    f,
+                    "target needs a command amount beyond {} \
+                     and this planner does not split amounts",
+                    u32::MAX
+                )
+            }
+            PlanError::VerificationFailed(position) => write!(
+                f,
+                "plan ends at {};{} instead of the target",
+                position.horizontal_pos, position.depth
+            ),
+        }
+    }
+}
+
+impl Error for PlanError {}
+
+fn command(direction: Direction, amount: i64) -> Result<NavigationCommand, PlanError> {
+    let amount = u32::try_from(amount).map_err(|_| PlanError::AmountOutOfRange)?;
+    Ok(NavigationCommand { direction, amount })
+}
+
+/// `down` or `up` command changing the aim by `change`.
+fn steer(change: i64) -> Result<NavigationCommand, PlanError> {
+    if change < 0 {
+        command(Direction::Up, -change)
+    } else {
+        command(Direction::Down, change)
+    }
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Miller-Rabin with the witnesses that are exact for every `u64`.
+fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&witness) = WITNESSES.iter().find(|&&x| n.is_multiple_of(x)) {
+        return n == witness;
+    }
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+    WITNESSES.iter().all(|&witness| {
+        let mut x = pow_mod(witness, odd, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        (1..shift).any(|_| {
+            x = mul_mod(x, x, n);
+            x == n - 1
+        })
+    })
+}
+
+/// Some non trivial factor of the odd composite `n`, by Pollard's rho.
+fn find_factor(n: u64) -> u64 {
+    for increment in 1.. {
+        let step = |x: u64| ((x as u128 * x as u128 + increment as u128) % n as u128) as u64;
+        let (mut slow, mut fast, mut factor) = (2, 2, 1);
+        while factor == 1 {
+            slow = step(slow);
+            fast = step(step(fast));
+            factor = gcd(slow.abs_diff(fast), n);
+        }
+        if factor != n {
+            return factor;
+        }
+    }
+    unreachable!()
+}
+
+fn prime_factors(n: u64, factors: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if n.is_multiple_of(2) {
+        factors.push(2);
+        prime_factors(n / 2, factors);
+    } else if is_prime(n) {
+        factors.push(n);
+    } else {
+        let factor = find_factor(n);
+        prime_factors(factor, factors);
+        prime_factors(n / factor, factors);
+    }
+}
+
+/// Every divisor of `n > 0`, unordered.
+fn divisors(n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    prime_factors(n, &mut factors);
+    factors.sort_unstable();
+    let mut divisors = vec![1];
+    let mut index = 0;
+    while index < factors.len() {
+        let prime = factors[index];
+        let count = factors[index..].iter().take_while(|&&x| x == prime).count();
+        let previous = divisors.len();
+        let mut power = 1;
+        for _ in 0..count {
+            power *= prime;
+            for known in 0..previous {
+                divisors.push(divisors[known] * power);
+            }
+        }
+        index += count;
+    }
+    divisors
+}
+
+/// Largest divisor `f` of `depth` usable for `forward h-f, down d/f,
+/// forward f`: below `horizontal`, with both forward amounts and the aim
+/// `depth / f` fitting into a command amount.
+///
+/// Factorizes `depth` instead of trying candidates, so the time does not
+/// depend on how many amounts would fit.
+fn largest_divisor_below(depth: u64, horizontal: u64) -> Option<u64> {
+    let max_amount = u32::MAX as u64;
+    let low = depth
+        .div_ceil(max_amount)
+        .max(horizontal.saturating_sub(max_amount))
+        .max(1);
+    let high = (horizontal - 1).min(depth).min(max_amount);
+    if low > high {
+        return None;
+    }
+    divisors(depth)
+        .into_iter()
+        .filter(|divisor| (low..=high).contains(divisor))
+        .max()
+}
+
+/// Synthesizes a shortest command list that brings a submarine following
+/// the aim rules from the origin to `horizontal;depth`, preferring the
+/// smallest total amount among equally short lists.
+///
+/// Only forward commands change the depth, by the current aim each, so:
+/// - a target at the origin needs no command, a target on the surface one,
+/// - `down d/h, forward h` works when `h` divides `d`,
+/// - `forward h-f, down d/f, forward f` works for a divisor `f < h` of `d`,
+/// - any other target is reached by splitting `h` between the two aims
+///   around `d/h` in four commands, the outer aim first.
+///
+/// Amounts are never split over several commands, so targets needing a
+/// forward or aim change beyond `u32::MAX` fail with
+/// [`PlanError::AmountOutOfRange`]. The plan is replayed through
+/// [`Submarine::execute_command`] before it is returned.
+pub fn plan(horizontal: i64, depth: i64) -> Result<Vec<NavigationCommand>, PlanError> {
+    if horizontal < 0 || (horizontal == 0 && depth != 0) {
+        return Err(PlanError::Unreachable);
+    }
+    let commands = if horizontal == 0 {
+        Vec::new()
+    } else if depth == 0 {
+        vec![command(Direction::Forward, horizontal)?]
+    } else if depth % horizontal == 0 && depth / horizontal <= u32::MAX as i64 {
+        vec![
+            steer(depth / horizontal)?,
+            command(Direction::Forward, horizontal)?,
+        ]
+    } else if let Some(divisor) =
+        largest_divisor_below(depth.unsigned_abs(), horizontal as u64).map(|x| x as i64)
+    {
+        vec![
+            command(Direction::Forward, horizontal - divisor)?,
+            steer(depth / divisor)?,
+            command(Direction::Forward, divisor)?,
+        ]
+    } else {
+        // Truncating towards zero keeps the first aim closest to the
+        // surface, which saves one on the aim changes for negative depths.
+        let aim = depth / horizontal;
+        let remainder = (depth % horizontal).abs();
+        vec![
+            steer(aim)?,
+            command(Direction::Forward, horizontal - remainder)?,
+            steer(depth.signum())?,
+            command(Direction::Forward, remainder)?,
+        ]
+    };
+
+    let mut submarine = Submarine::new(Box::new(AimModel), SurfacePolicy::Allow);
+    for command in &commands {
+        submarine
+            .execute_command(command)
+            .map_err(|_| PlanError::VerificationFailed(*submarine.position()))?;
+    }
+    let position = *submarine.position();
+    if position.horizontal_pos != horizontal || position.depth != depth {
+        return Err(PlanError::VerificationFailed(position));
+    }
+    Ok(commands)
+}
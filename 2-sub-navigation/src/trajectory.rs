@@ -0,0 +1,186 @@
+use crate::Position;
+use std::fmt::Write;
+
+const SVG_WIDTH: f64 = 960.0;
+const SVG_HEIGHT: f64 = 480.0;
+const SVG_MARGIN: f64 = 40.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrajectoryFormat {
+    Csv,
+    Json,
+    Svg,
+}
+
+impl TrajectoryFormat {
+    pub fn new(text: &str) -> Result<TrajectoryFormat, &'static str> {
+        match text {
+            "csv" => Ok(TrajectoryFormat::Csv),
+            "json" => Ok(TrajectoryFormat::Json),
+            "svg" => Ok(TrajectoryFormat::Svg),
+            _ => Err("Unknown trajectory format"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrajectorySample {
+    pub step: usize,
+    pub horizontal_pos: i64,
+    pub depth: i64,
+    pub aim: i64,
+    pub lateral_pos: i64,
+    pub heading: u32,
+}
+
+/// Positions of a run, starting with the initial position as step 0 and
+/// followed by one sample per executed command.
+#[derive(Debug, Default)]
+pub struct Trajectory {
+    samples: Vec<TrajectorySample>,
+}
+
+impl Trajectory {
+    pub fn new(start: &Position) -> Trajectory {
+        let mut trajectory = Trajectory::default();
+        trajectory.record(start);
+        trajectory
+    }
+
+    pub fn record(&mut self, position: &Position) {
+        self.samples.push(TrajectorySample {
+            step: self.samples.len(),
+            horizontal_pos: position.horizontal_pos,
+            depth: position.depth,
+            aim: position.aim,
+            lateral_pos: position.lateral_pos,
+            heading: position.heading,
+        });
+    }
+
+    pub fn samples(&self) -> &[TrajectorySample] {
+        &self.samples
+    }
+
+    pub fn export(&self, format: TrajectoryFormat) -> String {
+        match format {
+            TrajectoryFormat::Csv => self.to_csv(),
+            TrajectoryFormat::Json => self.to_json(),
+            TrajectoryFormat::Svg => self.to_svg(),
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,horizontal,depth,aim,lateral,heading\n");
+        for sample in &self.samples {
+            writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                sample.step,
+                sample.horizontal_pos,
+                sample.depth,
+                sample.aim,
+                sample.lateral_pos,
+                sample.heading
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        let samples = self
+            .samples
+            .iter()
+            .map(|sample| {
+                format!(
+                    "{{\"step\":{},\"horizontal\":{},\"depth\":{},\"aim\":{},\
+                     \"lateral\":{},\"heading\":{}}}",
+                    sample.step,
+                    sample.horizontal_pos,
+                    sample.depth,
+                    sample.aim,
+                    sample.lateral_pos,
+                    sample.heading
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        format!("[{}]\n", samples)
+    }
+
+    /// Dive profile as a polyline of depth over horizontal position, with
+    /// depth growing downwards. The dashed line marks the surface when it is
+    /// in view.
+    pub fn to_svg(&self) -> String {
+        let min_x = self
+            .samples
+            .iter()
+            .map(|x| x.horizontal_pos)
+            .min()
+            .unwrap_or(0);
+        let max_x = self
+            .samples
+            .iter()
+            .map(|x| x.horizontal_pos)
+            .max()
+            .unwrap_or(0);
+        let min_y = self
+            .samples
+            .iter()
+            .map(|x| x.depth)
+            .min()
+            .unwrap_or(0)
+            .min(0);
+        let max_y = self.samples.iter().map(|x| x.depth).max().unwrap_or(0);
+        let x_range = (max_x - min_x).max(1) as f64;
+        let y_range = (max_y - min_y).max(1) as f64;
+        let plot_width = SVG_WIDTH - 2.0 * SVG_MARGIN;
+        let plot_height = SVG_HEIGHT - 2.0 * SVG_MARGIN;
+        let project = |x: i64, y: i64| {
+            (
+                SVG_MARGIN + (x - min_x) as f64 / x_range * plot_width,
+                SVG_MARGIN + (y - min_y) as f64 / y_range * plot_height,
+            )
+        };
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\" font-family=\"monospace\" font-size=\"12\">",
+            SVG_WIDTH, SVG_HEIGHT, SVG_WIDTH, SVG_HEIGHT
+        )
+        .unwrap();
+        writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>").unwrap();
+        let (_, surface) = project(min_x, 0);
+        writeln!(
+            svg,
+            "<line x1=\"{}\" y1=\"{:.2}\" x2=\"{}\" y2=\"{:.2}\" stroke=\"#999\" \
+             stroke-dasharray=\"4 4\"/>",
+            SVG_MARGIN,
+            surface,
+            SVG_WIDTH - SVG_MARGIN,
+            surface
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\">horizontal {}..{}, depth {}..{}</text>",
+            SVG_MARGIN,
+            SVG_MARGIN - 12.0,
+            min_x,
+            max_x,
+            min_y,
+            max_y
+        )
+        .unwrap();
+        svg.push_str("<polyline fill=\"none\" stroke=\"steelblue\" stroke-width=\"1\" points=\"");
+        for sample in &self.samples {
+            let (x, y) = project(sample.horizontal_pos, sample.depth);
+            write!(svg, "{:.2},{:.2} ", x, y).unwrap();
+        }
+        svg.push_str("\"/>\n</svg>\n");
+        svg
+    }
+}
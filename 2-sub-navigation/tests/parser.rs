@@ -0,0 +1,123 @@
+use sub_navigation::parser::{
+    parse_commands, parse_script, NavigationParseError, ParseErrorReason,
+};
+
+#[test]
+fn blank_lines_are_skipped() {
+    let commands = parse_commands("forward 5\n\n  \ndown 3\n").unwrap();
+    assert_eq!(commands.len(), 2);
+    assert_eq!(commands[1].amount, 3);
+}
+
+#[test]
+fn every_bad_line_is_reported() {
+    let errors = parse_commands("forward x\ndown 2\nsideways 3\n  up\nup 1 2\n").unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            NavigationParseError {
+                line: 1,
+                column: 9,
+                reason: ParseErrorReason::InvalidAmount("x".to_string()),
+            },
+            NavigationParseError {
+                line: 3,
+                column: 1,
+                reason: ParseErrorReason::UnknownDirection("sideways".to_string()),
+            },
+            NavigationParseError {
+                line: 4,
+                column: 5,
+                reason: ParseErrorReason::MissingAmount,
+            },
+            NavigationParseError {
+                line: 5,
+                column: 6,
+                reason: ParseErrorReason::UnexpectedToken("2".to_string()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn macros_and_repeats_expand() {
+    let script = "\
+# dive twice, then level out
+macro dive {
+    down 2   # steeper
+    forward 3
+}
+repeat 2 {
+    dive
+    repeat 3 {
+        up 1
+    }
+}
+forward 1
+";
+    let commands = parse_script(script).unwrap();
+    let lines: Vec<usize> = commands.iter().map(|x| x.line).collect();
+    assert_eq!(lines, vec![3, 4, 9, 9, 9, 3, 4, 9, 9, 9, 12]);
+    assert_eq!(commands[1].command.amount, 3);
+}
+
+#[test]
+fn script_errors_point_at_source_lines() {
+    let script = "\
+repeat x {
+    dive
+}
+macro up {
+}
+repeat 2 {
+    forward 1
+}
+}
+macro outer {
+    macro inner {
+    }
+}
+repeat 1 {
+";
+    let errors = parse_script(script).unwrap_err();
+    let reasons: Vec<(usize, usize, ParseErrorReason)> = errors
+        .into_iter()
+        .map(|x| (x.line, x.column, x.reason))
+        .collect();
+    assert_eq!(
+        reasons,
+        vec![
+            (1, 8, ParseErrorReason::InvalidRepeatCount("x".to_string())),
+            (2, 5, ParseErrorReason::UnknownMacro("dive".to_string())),
+            (4, 7, ParseErrorReason::InvalidMacroName("up".to_string())),
+            (9, 1, ParseErrorReason::UnmatchedClosingBrace),
+            (11, 5, ParseErrorReason::NestedMacro),
+            (14, 1, ParseErrorReason::UnclosedBlock),
+        ]
+    );
+}
+
+#[test]
+fn expansion_is_limited() {
+    let script = "repeat 100000 {\n    repeat 1000 {\n        forward 1\n    }\n}\n";
+    let errors = parse_script(script).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        (errors[0].line, errors[0].column, &errors[0].reason),
+        (1, 1, &ParseErrorReason::ExpansionLimit)
+    );
+
+    let script =
+        "macro twice {\n    forward 1\n    forward 1\n}\nrepeat 4000000000 {\n    twice\n}\n";
+    let errors = parse_script(script).unwrap_err();
+    assert_eq!((errors[0].line, errors[0].column), (5, 1));
+}
+
+#[test]
+fn empty_bodies_expand_instantly() {
+    let script = "repeat 4000000000 {\n    repeat 4000000000 {\n    }\n}\n\
+                  macro nop {\n}\nrepeat 4000000000 {\n    nop\n}\nforward 3\n";
+    let commands = parse_script(script).unwrap();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].line, 10);
+}
@@ -0,0 +1,145 @@
+use std::collections::{HashMap, HashSet};
+use sub_navigation::planner::{plan, PlanError};
+use sub_navigation::{AimModel, Direction, NavigationCommand, NavigationModel, Position};
+
+const MAX_HORIZONTAL: i64 = 12;
+const MAX_DEPTH: i64 = 20;
+const MAX_AMOUNT: u32 = 20;
+
+/// Shortest command count to every position within the bounds and the
+/// smallest total amount among lists of that count, found by a breadth
+/// first search over (horizontal, depth, aim).
+fn shortest_lengths() -> HashMap<(i64, i64), (usize, u64)> {
+    let mut seen = HashSet::from([(0, 0, 0)]);
+    let mut lengths = HashMap::new();
+    let mut layer = vec![(Position::default(), 0)];
+    let mut length = 0;
+    while !layer.is_empty() {
+        let mut next_layer: HashMap<(i64, i64, i64), (Position, u64)> = HashMap::new();
+        for (position, total) in layer {
+            let best = lengths
+                .entry((position.horizontal_pos, position.depth))
+                .or_insert((length, total));
+            if best.0 == length {
+                best.1 = best.1.min(total);
+            }
+            for direction in [Direction::Forward, Direction::Down, Direction::Up] {
+                for amount in 1..=MAX_AMOUNT {
+                    let command = NavigationCommand { direction, amount };
+                    let next = AimModel.apply(&position, &command).unwrap();
+                    if next.horizontal_pos > MAX_HORIZONTAL
+                        || next.depth.abs() > 2 * MAX_DEPTH
+                        || next.aim.abs() > MAX_DEPTH
+                        || seen.contains(&(next.horizontal_pos, next.depth, next.aim))
+                    {
+                        continue;
+                    }
+                    let key = (next.horizontal_pos, next.depth, next.aim);
+                    let entry = next_layer.entry(key).or_insert((next, u64::MAX));
+                    entry.1 = entry.1.min(total + amount as u64);
+                }
+            }
+        }
+        seen.extend(next_layer.keys().copied());
+        layer = next_layer.into_values().collect();
+        length += 1;
+    }
+    lengths
+}
+
+#[test]
+fn plans_are_shortest() {
+    let lengths = shortest_lengths();
+    for horizontal in 0..=MAX_HORIZONTAL {
+        for depth in -MAX_DEPTH..=MAX_DEPTH {
+            match plan(horizontal, depth) {
+                Ok(commands) => {
+                    let total = commands.iter().map(|x| x.amount as u64).sum();
+                    assert_eq!(
+                        Some(&(commands.len(), total)),
+                        lengths.get(&(horizontal, depth)),
+                        "{};{}: {:?}",
+                        horizontal,
+                        depth,
+                        commands
+                    )
+                }
+                Err(err) => {
+                    assert_eq!(err, PlanError::Unreachable);
+                    assert!(!lengths.contains_key(&(horizontal, depth)));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn three_command_plans_use_the_largest_divisor() {
+    let commands: Vec<String> = plan(10, 12)
+        .unwrap()
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(commands, vec!["forward 4", "down 2", "forward 6"]);
+}
+
+#[test]
+fn large_targets() {
+    // A prime depth too large to be an aim only has the four command plan.
+    assert_eq!(plan(1_000_000, 10_000_000_019).unwrap().len(), 4);
+    assert_eq!(
+        plan(u32::MAX as i64 + 1, 0),
+        Err(PlanError::AmountOutOfRange)
+    );
+    assert_eq!(plan(-1, 0), Err(PlanError::Unreachable));
+
+    // Horizontal positions beyond one command amount still work when both
+    // forward commands fit.
+    let commands: Vec<String> = plan(5_000_000_000, 4_000_000_000)
+        .unwrap()
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(
+        commands,
+        vec!["forward 1000000000", "down 1", "forward 4000000000"]
+    );
+
+    // Needs amounts to be split, which is rejected without a divisor search.
+    let err = plan(1_000_000_000_000_000_000, 9_223_372_036_854_775_783).unwrap_err();
+    assert_eq!(err, PlanError::AmountOutOfRange);
+    assert!(err.to_string().contains("does not split amounts"));
+
+    // Negative depths mirror the positive four command plan.
+    let commands: Vec<String> = plan(1_000_000, -10_000_000_019)
+        .unwrap()
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(
+        commands,
+        vec!["up 10000", "forward 999981", "up 1", "forward 19"]
+    );
+}
+
+#[test]
+fn divisor_search_factorizes_large_depths() {
+    // Far more candidate divisors fit than could be tried one by one, and
+    // none of them divides the depth.
+    let (horizontal, depth) = (4_000_000_000, 1_000_000_000_000_000_003);
+    assert_eq!(plan(horizontal, depth).unwrap().len(), 4);
+    // A product of two primes just below 2^31, both of which fit.
+    let commands: Vec<String> = plan(horizontal, 2_147_483_647 * 2_147_483_629)
+        .unwrap()
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(
+        commands,
+        vec![
+            "forward 1852516353",
+            "down 2147483629",
+            "forward 2147483647"
+        ]
+    );
+}
@@ -0,0 +1,205 @@
+use sub_navigation::{
+    AimModel, DirectModel, Direction, HeadingModel, NavigationCommand, NavigationError,
+    NavigationModel, Position, Submarine, SurfacePolicy,
+};
+
+const EXAMPLE: [&str; 6] = [
+    "forward 5",
+    "down 5",
+    "forward 8",
+    "up 3",
+    "down 8",
+    "forward 2",
+];
+
+fn command(text: &str) -> NavigationCommand {
+    NavigationCommand::new(text, 1).unwrap()
+}
+
+fn run(
+    model: Box<dyn NavigationModel>,
+    surface_policy: SurfacePolicy,
+    commands: &[&str],
+) -> (Submarine, Result<(), NavigationError>) {
+    let mut submarine = Submarine::new(model, surface_policy);
+    for text in commands {
+        if let Err(err) = submarine.execute_command(&command(text)) {
+            return (submarine, Err(err));
+        }
+    }
+    (submarine, Ok(()))
+}
+
+#[test]
+fn example_direct_model() {
+    let (submarine, result) = run(Box::new(DirectModel), SurfacePolicy::Forbid, &EXAMPLE);
+    assert_eq!(result, Ok(()));
+    assert_eq!(submarine.position().horizontal_pos, 15);
+    assert_eq!(submarine.position().depth, 10);
+    assert_eq!(submarine.position().product(), Ok(150));
+}
+
+#[test]
+fn example_aim_model() {
+    let (submarine, result) = run(Box::new(AimModel), SurfacePolicy::Forbid, &EXAMPLE);
+    assert_eq!(result, Ok(()));
+    assert_eq!(submarine.position().depth, 60);
+    assert_eq!(submarine.position().product(), Ok(900));
+}
+
+#[test]
+fn up_beyond_aim_points_upwards() {
+    let (submarine, result) = run(
+        Box::new(AimModel),
+        SurfacePolicy::Allow,
+        &["down 2", "up 5", "forward 3"],
+    );
+    assert_eq!(result, Ok(()));
+    assert_eq!(
+        *submarine.position(),
+        Position {
+            horizontal_pos: 3,
+            depth: -9,
+            aim: -3,
+            ..Position::default()
+        }
+    );
+}
+
+#[test]
+fn surfacing_is_reported_when_forbidden() {
+    let (submarine, result) = run(
+        Box::new(DirectModel),
+        SurfacePolicy::Forbid,
+        &["down 4", "up 5"],
+    );
+    assert_eq!(result, Err(NavigationError::AboveSurface { depth: -1 }));
+    assert_eq!(submarine.position().depth, 4);
+
+    let (submarine, result) = run(
+        Box::new(AimModel),
+        SurfacePolicy::Forbid,
+        &["up 1", "forward 1"],
+    );
+    assert_eq!(result, Err(NavigationError::AboveSurface { depth: -1 }));
+    assert_eq!(submarine.position().horizontal_pos, 0);
+}
+
+#[test]
+fn surface_itself_is_allowed() {
+    let (submarine, result) = run(
+        Box::new(DirectModel),
+        SurfacePolicy::Forbid,
+        &["down 4", "up 4"],
+    );
+    assert_eq!(result, Ok(()));
+    assert_eq!(submarine.position().depth, 0);
+}
+
+#[test]
+fn depth_overflow_is_an_error() {
+    let down = format!("down {}", u32::MAX);
+    let forward = format!("forward {}", u32::MAX);
+    let (submarine, result) = run(
+        Box::new(AimModel),
+        SurfacePolicy::Forbid,
+        &[&down, &down, &down, &forward],
+    );
+    assert_eq!(result, Err(NavigationError::Overflow));
+    // The failing command did not partially update the position.
+    assert_eq!(submarine.position().horizontal_pos, 0);
+    assert_eq!(submarine.position().aim, 3 * u32::MAX as i64);
+}
+
+#[test]
+fn product_overflow_is_an_error() {
+    let position = Position {
+        horizontal_pos: i64::MAX / 2,
+        depth: 3,
+        ..Position::default()
+    };
+    assert_eq!(position.product(), Err(NavigationError::Overflow));
+}
+
+#[test]
+fn trajectory_has_one_sample_per_command() {
+    let mut submarine = Submarine::new(Box::new(AimModel), SurfacePolicy::Allow);
+    submarine.record_trajectory();
+    for text in EXAMPLE {
+        submarine.execute_command(&command(text)).unwrap();
+    }
+    let samples = submarine.trajectory().unwrap().samples();
+    assert_eq!(samples.len(), EXAMPLE.len() + 1);
+    assert_eq!(samples[0].depth, 0);
+    assert_eq!(samples[3].step, 3);
+    assert_eq!(samples[3].horizontal_pos, 13);
+    assert_eq!(samples[3].depth, 40);
+    assert_eq!(samples[6].aim, 10);
+    assert!(submarine
+        .trajectory()
+        .unwrap()
+        .to_csv()
+        .ends_with("6,15,60,10,0,0\n"));
+}
+
+#[test]
+fn heading_model_matches_aim_model_without_turns() {
+    let (heading, result) = run(Box::new(HeadingModel), SurfacePolicy::Forbid, &EXAMPLE);
+    assert_eq!(result, Ok(()));
+    let (aim, _) = run(Box::new(AimModel), SurfacePolicy::Forbid, &EXAMPLE);
+    assert_eq!(heading.position(), aim.position());
+}
+
+#[test]
+fn heading_model_moves_in_three_dimensions() {
+    let (submarine, result) = run(
+        Box::new(HeadingModel),
+        SurfacePolicy::Forbid,
+        &[
+            "down 1",
+            "forward 4",
+            "right 90",
+            "forward 3",
+            "left 270",
+            "forward 2",
+            "turn 270",
+            "forward 1",
+        ],
+    );
+    assert_eq!(result, Ok(()));
+    assert_eq!(
+        *submarine.position(),
+        Position {
+            horizontal_pos: 2,
+            lateral_pos: 2,
+            depth: 10,
+            aim: 1,
+            heading: 270,
+        }
+    );
+}
+
+#[test]
+fn turns_must_be_quarter_turns() {
+    let (submarine, result) = run(
+        Box::new(HeadingModel),
+        SurfacePolicy::Allow,
+        &["right 90", "left 45"],
+    );
+    assert_eq!(result, Err(NavigationError::InvalidHeading(45)));
+    assert_eq!(submarine.position().heading, 90);
+}
+
+#[test]
+fn plane_models_reject_turns() {
+    for model in [
+        Box::new(DirectModel) as Box<dyn NavigationModel>,
+        Box::new(AimModel),
+    ] {
+        let (_, result) = run(model, SurfacePolicy::Allow, &["forward 1", "turn 90"]);
+        assert_eq!(
+            result,
+            Err(NavigationError::UnsupportedCommand(Direction::Turn))
+        );
+    }
+}
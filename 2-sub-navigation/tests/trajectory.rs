@@ -0,0 +1,61 @@
+use sub_navigation::trajectory::{Trajectory, TrajectoryFormat};
+use sub_navigation::Position;
+
+fn trajectory(points: &[(i64, i64)]) -> Trajectory {
+    let mut trajectory = Trajectory::new(&Position::default());
+    for &(horizontal_pos, depth) in points {
+        trajectory.record(&Position {
+            horizontal_pos,
+            depth,
+            aim: depth / 2,
+            ..Position::default()
+        });
+    }
+    trajectory
+}
+
+fn polyline_points(svg: &str) -> Vec<(f64, f64)> {
+    let start = svg.find("points=\"").unwrap() + "points=\"".len();
+    let end = start + svg[start..].find('"').unwrap();
+    svg[start..end]
+        .split_whitespace()
+        .map(|point| {
+            let (x, y) = point.split_once(',').unwrap();
+            (x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn json_has_one_object_per_sample() {
+    let json = trajectory(&[(5, 0), (13, -4)]).export(TrajectoryFormat::Json);
+    assert_eq!(
+        json,
+        "[{\"step\":0,\"horizontal\":0,\"depth\":0,\"aim\":0,\"lateral\":0,\"heading\":0},\
+         {\"step\":1,\"horizontal\":5,\"depth\":0,\"aim\":0,\"lateral\":0,\"heading\":0},\
+         {\"step\":2,\"horizontal\":13,\"depth\":-4,\"aim\":-2,\"lateral\":0,\"heading\":0}]\n"
+    );
+    assert_eq!(Trajectory::default().to_json(), "[]\n");
+}
+
+#[test]
+fn svg_plots_every_sample_below_the_surface() {
+    let svg = trajectory(&[(5, 0), (13, 40), (15, 60)]).to_svg();
+    let points = polyline_points(&svg);
+    assert_eq!(points.len(), 4);
+    assert_eq!(points[0], (40.0, 40.0));
+    assert_eq!(points[3], (920.0, 440.0));
+    // All depths are positive, so the surface is the top of the plot.
+    assert!(svg.contains("<line x1=\"40\" y1=\"40.00\" x2=\"920\" y2=\"40.00\""));
+    assert!(svg.contains("horizontal 0..15, depth 0..60"));
+}
+
+#[test]
+fn svg_surface_moves_down_for_negative_depths() {
+    let svg = trajectory(&[(10, -20), (20, 20)]).to_svg();
+    let points = polyline_points(&svg);
+    assert_eq!(points, vec![(40.0, 240.0), (480.0, 40.0), (920.0, 440.0)]);
+    assert!(svg.contains("<line x1=\"40\" y1=\"240.00\" x2=\"920\" y2=\"240.00\""));
+    assert!(svg.contains("depth -20..20"));
+    assert!(svg.ends_with("</svg>\n"));
+}
@@ -0,0 +1,59 @@
+//! Compares the retain based rating filter with in-place partitioning on
+//! large generated reports. Run with `cargo bench`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::XorShift;
+use power_consumption::partition::partitioned_ratings;
+use power_consumption::{
+    filter_rating, BitOrder, DiagnosticReport, CO2_SCRUBBER_RULE, OXYGEN_GENERATOR_RULE,
+};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WIDTH: usize = 32;
+const REPORT_SIZES: [usize; 3] = [10_000, 100_000, 2_000_000];
+const RUNS: u32 = 5;
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        f();
+    }
+    start.elapsed() / RUNS
+}
+
+fn main() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    for size in REPORT_SIZES {
+        let report = DiagnosticReport {
+            width: WIDTH,
+            numbers: (0..size)
+                .map(|_| (rng.next() % (1 << WIDTH)) as u128)
+                .collect(),
+        };
+
+        let retain = time(|| {
+            for rule in [OXYGEN_GENERATOR_RULE, CO2_SCRUBBER_RULE] {
+                black_box(filter_rating(
+                    black_box(&report.numbers),
+                    WIDTH,
+                    BitOrder::MsbFirst,
+                    rule,
+                ));
+            }
+        });
+        let partition = time(|| {
+            black_box(partitioned_ratings(
+                black_box(&report.numbers),
+                WIDTH,
+                [OXYGEN_GENERATOR_RULE, CO2_SCRUBBER_RULE],
+            ));
+        });
+        println!(
+            "{:>9} numbers: retain {:>10.3?}, partition {:>10.3?}",
+            size, retain, partition
+        );
+    }
+}
@@ -0,0 +1,321 @@
+pub mod partition;
+pub mod trace;
+
+use std::fmt;
+
+pub const MAX_BIT_WIDTH: usize = 128;
+
+#[derive(Debug, PartialEq)]
+pub enum ReportError {
+    Empty,
+    TooWide {
+        line: usize,
+        width: usize,
+    },
+    MixedWidth {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    NonBinary {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReportError::Empty => write!(f, "report contains no numbers"),
+            ReportError::TooWide { line, width } => write!(
+                f,
+                "line {}: {} bits exceed the supported {} bits",
+                line, width, MAX_BIT_WIDTH
+            ),
+            ReportError::MixedWidth {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} bits like the first line, found {}",
+                line, expected, found
+            ),
+            ReportError::NonBinary {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {}:{}: '{}' is not a binary digit",
+                line, column, found
+            ),
+        }
+    }
+}
+
+/// Diagnostic numbers that all share the bit width of the first line.
+#[derive(Debug)]
+pub struct DiagnosticReport {
+    pub width: usize,
+    pub numbers: Vec<u128>,
+}
+
+impl DiagnosticReport {
+    pub fn new<I>(lines: I) -> Result<DiagnosticReport, ReportError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut width = None;
+        let mut numbers = Vec::new();
+        for (index, line) in lines.into_iter().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some((column, found)) = line.chars().enumerate().find(|x| !"01".contains(x.1)) {
+                return Err(ReportError::NonBinary {
+                    line: line_number,
+                    column: column + 1,
+                    found,
+                });
+            }
+            let line_width = line.len();
+            if line_width > MAX_BIT_WIDTH {
+                return Err(ReportError::TooWide {
+                    line: line_number,
+                    width: line_width,
+                });
+            }
+            let expected = *width.get_or_insert(line_width);
+            if line_width != expected {
+                return Err(ReportError::MixedWidth {
+                    line: line_number,
+                    expected,
+                    found: line_width,
+                });
+            }
+            numbers.push(u128::from_str_radix(line, 2).unwrap());
+        }
+        match width {
+            Some(width) => Ok(DiagnosticReport { width, numbers }),
+            None => Err(ReportError::Empty),
+        }
+    }
+}
+
+pub fn count_occurences(report: &[u128], bit: usize) -> (usize, usize) {
+    let number_of_ones = report.iter().filter(|&&x| has_bit_set(x, bit)).count();
+    (report.len() - number_of_ones, number_of_ones)
+}
+
+pub fn has_bit_set(val: u128, bit: usize) -> bool {
+    ((val >> bit) & 1) == 1
+}
+
+/// Part 1 and part 2 results of a diagnostic report. Products are `None`
+/// when they do not fit into 128 bits, which only wide reports can cause.
+#[derive(Debug, PartialEq)]
+pub struct DiagnosticSummary {
+    pub gamma_rate: u128,
+    pub epsilon_rate: u128,
+    pub power_consumption: Option<u128>,
+    pub oxygen_generator_rating: u128,
+    pub co2_scrubber_rating: u128,
+    pub life_support_rating: Option<u128>,
+}
+
+impl fmt::Display for DiagnosticSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let product = |x: Option<u128>| x.map_or("overflow".to_string(), |x| x.to_string());
+        writeln!(f, "Gamma rate:              {}", self.gamma_rate)?;
+        writeln!(f, "Epsilon rate:            {}", self.epsilon_rate)?;
+        writeln!(
+            f,
+            "Power consumption:       {}",
+            product(self.power_consumption)
+        )?;
+        writeln!(
+            f,
+            "Oxygen generator rating: {}",
+            self.oxygen_generator_rating
+        )?;
+        writeln!(f, "CO2 scrubber rating:     {}", self.co2_scrubber_rating)?;
+        write!(
+            f,
+            "Life support rating:     {}",
+            product(self.life_support_rating)
+        )
+    }
+}
+
+fn width_mask(width: usize) -> u128 {
+    if width == MAX_BIT_WIDTH {
+        u128::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Gamma rate built from the most common bit of every column, a tie counts
+/// as a one. Epsilon is its complement within the report width.
+pub fn gamma_epsilon_rates(report: &DiagnosticReport) -> (u128, u128) {
+    let mut gamma_rate = 0;
+    for bit in 0..report.width {
+        let (zero_occ, one_occ) = count_occurences(&report.numbers, bit);
+        if one_occ >= zero_occ {
+            gamma_rate |= 1 << bit;
+        }
+    }
+    (gamma_rate, !gamma_rate & width_mask(report.width))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BitCriteria {
+    MostCommon,
+    LeastCommon,
+}
+
+/// Which numbers survive a round where ones and zeros are equally common.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TieBreak {
+    KeepOnes,
+    KeepZeros,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FilterRule {
+    pub criteria: BitCriteria,
+    pub tie_break: TieBreak,
+}
+
+pub const OXYGEN_GENERATOR_RULE: FilterRule = FilterRule {
+    criteria: BitCriteria::MostCommon,
+    tie_break: TieBreak::KeepOnes,
+};
+
+pub const CO2_SCRUBBER_RULE: FilterRule = FilterRule {
+    criteria: BitCriteria::LeastCommon,
+    tie_break: TieBreak::KeepZeros,
+};
+
+impl FilterRule {
+    /// Bit value the numbers must have to survive a round. A value none of
+    /// the candidates has is never chosen, so a round can not remove them all.
+    pub fn kept_bit(&self, zero_occ: usize, one_occ: usize) -> bool {
+        if zero_occ == 0 || one_occ == 0 {
+            return one_occ > 0;
+        }
+        if zero_occ == one_occ {
+            return self.tie_break == TieBreak::KeepOnes;
+        }
+        match self.criteria {
+            BitCriteria::MostCommon => one_occ > zero_occ,
+            BitCriteria::LeastCommon => one_occ < zero_occ,
+        }
+    }
+}
+
+/// Bit positions of a `width` bit number in the given order.
+pub fn bit_positions(width: usize, order: BitOrder) -> Box<dyn Iterator<Item = usize>> {
+    match order {
+        BitOrder::MsbFirst => Box::new((0..width).rev()),
+        BitOrder::LsbFirst => Box::new(0..width),
+    }
+}
+
+/// Applies the bit criteria process: for every bit in `order`, counted
+/// among the remaining candidates, only numbers matching `rule` are kept
+/// until a single number is left. `None` if there are no numbers; if
+/// duplicates remain after the last bit the first of them is returned.
+pub fn filter_rating(
+    numbers: &[u128],
+    width: usize,
+    order: BitOrder,
+    rule: FilterRule,
+) -> Option<u128> {
+    filter_candidates(numbers, width, order, rule, None)
+}
+
+/// One round of the bit criteria process.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FilterRound {
+    pub bit: usize,
+    pub zero_occ: usize,
+    pub one_occ: usize,
+    pub kept_bit: bool,
+    pub remaining: usize,
+}
+
+/// [`filter_rating`] that also returns every round it went through.
+pub fn filter_rating_traced(
+    numbers: &[u128],
+    width: usize,
+    order: BitOrder,
+    rule: FilterRule,
+) -> (Option<u128>, Vec<FilterRound>) {
+    let mut rounds = Vec::new();
+    let rating = filter_candidates(numbers, width, order, rule, Some(&mut rounds));
+    (rating, rounds)
+}
+
+fn filter_candidates(
+    numbers: &[u128],
+    width: usize,
+    order: BitOrder,
+    rule: FilterRule,
+    mut rounds: Option<&mut Vec<FilterRound>>,
+) -> Option<u128> {
+    let mut candidates = numbers.to_vec();
+    for bit in bit_positions(width, order) {
+        if candidates.len() <= 1 {
+            break;
+        }
+        let (zero_occ, one_occ) = count_occurences(&candidates, bit);
+        let kept_bit = rule.kept_bit(zero_occ, one_occ);
+        candidates.retain(|&x| has_bit_set(x, bit) == kept_bit);
+        if let Some(rounds) = rounds.as_mut() {
+            rounds.push(FilterRound {
+                bit,
+                zero_occ,
+                one_occ,
+                kept_bit,
+                remaining: candidates.len(),
+            });
+        }
+    }
+    candidates.first().copied()
+}
+
+/// Oxygen generator and CO2 scrubber ratings, filtering from the most
+/// significant bit as the puzzle describes.
+pub fn life_support_ratings(report: &DiagnosticReport) -> (u128, u128) {
+    let [oxygen_generator_rating, co2_scrubber_rating] = partition::partitioned_ratings(
+        &report.numbers,
+        report.width,
+        [OXYGEN_GENERATOR_RULE, CO2_SCRUBBER_RULE],
+    )
+    .expect("report is never empty");
+    (oxygen_generator_rating, co2_scrubber_rating)
+}
+
+pub fn diagnose(report: &DiagnosticReport) -> DiagnosticSummary {
+    let (gamma_rate, epsilon_rate) = gamma_epsilon_rates(report);
+    let (oxygen_generator_rating, co2_scrubber_rating) = life_support_ratings(report);
+    DiagnosticSummary {
+        gamma_rate,
+        epsilon_rate,
+        power_consumption: gamma_rate.checked_mul(epsilon_rate),
+        oxygen_generator_rating,
+        co2_scrubber_rating,
+        life_support_rating: oxygen_generator_rating.checked_mul(co2_scrubber_rating),
+    }
+}
@@ -1,65 +1,72 @@
-#![feature(vec_retain_mut)]
+use power_consumption::trace::{column_counts_csv, render_report};
+use power_consumption::{diagnose, DiagnosticReport, CO2_SCRUBBER_RULE, OXYGEN_GENERATOR_RULE};
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
+use std::process;
 
-fn count_occurences(report: &Vec<u32>, bit: u32) -> (u32, u32) {
-    let number_of_ones = report.into_iter().map(|x| (x & (1 << bit)) >> bit).sum();
-    (report.len() as u32 - number_of_ones, number_of_ones)
-}
+const USAGE: &str = "Usage: power-consumption [--rounds] [--column-csv FILE] REPORT";
 
-fn set_bit(val: u32, bit: u32, active: bool) -> u32 {
-    if active {
-        val | (1 << bit)
-    } else {
-        val & !(1 << bit)
-    }
+struct Options {
+    rounds: bool,
+    column_csv: Option<String>,
+    path: String,
 }
 
-fn has_bit_set(val: u32, bit: u32) -> bool {
-    ((val & (1 << bit)) >> bit) == 1
+impl Options {
+    fn new(args: &[String]) -> Result<Options, String> {
+        let mut rounds = false;
+        let mut column_csv = None;
+        let mut path = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--rounds" => rounds = true,
+                "--column-csv" => {
+                    column_csv = Some(args.next().ok_or("Missing value for --column-csv")?.clone());
+                }
+                _ if path.is_none() => path = Some(arg.clone()),
+                _ => return Err(format!("Unexpected argument: {}", arg)),
+            }
+        }
+        Ok(Options {
+            rounds,
+            column_csv,
+            path: path.ok_or("Provide the report input text file!")?,
+        })
+    }
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Provide the report input text file!");
-    }
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = Options::new(&args).unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(1);
+    });
 
-    let file = File::open(&args[1]).expect("Error opening file");
+    let file = File::open(&options.path).expect("Error opening file");
     let reader = BufReader::new(file);
-    let mut o2_list: Vec<u32> = reader
-        .lines()
-        .map(|x| u32::from_str_radix(&x.unwrap(), 2).expect("Error converting"))
-        .collect();
-
-    let mut co2_list = o2_list.clone();
+    let report = DiagnosticReport::new(reader.lines().map(|x| x.expect("Error reading line")))
+        .unwrap_or_else(|err| {
+            eprintln!("{}: {}", options.path, err);
+            process::exit(1);
+        });
 
-    for i in 0..12 {
-        let (zero_occ, one_occ) = count_occurences(&o2_list, i);
-        if one_occ > zero_occ {
-            if o2_list.len() > 1 {
-                o2_list.retain_mut(|x| has_bit_set(*x, i));
-            }
-            if co2_list.len() > 1 {
-                co2_list.retain_mut(|x| !has_bit_set(*x, i))
-            }
-        } else if zero_occ > one_occ {
-            if o2_list.len() > 1 {
-                o2_list.retain_mut(|x| !has_bit_set(*x, i));
-            }
-            if co2_list.len() > 1 {
-                co2_list.retain_mut(|x| has_bit_set(*x, i));
-            }
-        } else {
-            if o2_list.len() > 1 {
-                o2_list.retain_mut(|x| has_bit_set(*x, i));
-            }
-            if co2_list.len() > 1 {
-                co2_list.retain_mut(|x| !has_bit_set(*x, i));
-            }
-        }
+    println!("{}", diagnose(&report));
+    if options.rounds {
+        print!(
+            "\n{}",
+            render_report(
+                &report,
+                &[
+                    ("Oxygen generator", OXYGEN_GENERATOR_RULE),
+                    ("CO2 scrubber", CO2_SCRUBBER_RULE),
+                ]
+            )
+        );
+    }
+    if let Some(path) = options.column_csv {
+        fs::write(&path, column_counts_csv(&report)).expect("Error writing column counts");
     }
-    println!("O2 Codes: {:?}", o2_list);
-    println!("CO2 Codes: {:?}", co2_list);
 }
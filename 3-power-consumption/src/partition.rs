@@ -0,0 +1,56 @@
+use crate::{has_bit_set, FilterRule};
+
+/// Moves the numbers without `bit` set to the front and returns how many
+/// there are.
+fn partition(numbers: &mut [u128], bit: usize) -> usize {
+    let mut split = 0;
+    for index in 0..numbers.len() {
+        if !has_bit_set(numbers[index], bit) {
+            numbers.swap(split, index);
+            split += 1;
+        }
+    }
+    split
+}
+
+/// Applies every rule MSB-first in a single pass over the bits, giving the
+/// same ratings as [`crate::filter_rating`] with [`crate::BitOrder::MsbFirst`].
+/// `None` if there are no numbers.
+///
+/// All rules share one copy of the numbers. Each round partitions the
+/// candidate range of a rule in place by the current bit and keeps one
+/// side, so candidates stay a contiguous range and a round costs as much
+/// as the candidates left instead of a count plus a retain over them.
+/// Rules either agree on a side, when only one bit value is left, or
+/// split on it, so their ranges are always identical or disjoint.
+pub fn partitioned_ratings<const N: usize>(
+    numbers: &[u128],
+    width: usize,
+    rules: [FilterRule; N],
+) -> Option<[u128; N]> {
+    if numbers.is_empty() {
+        return None;
+    }
+    let mut numbers = numbers.to_vec();
+    let mut ranges = [(0, numbers.len()); N];
+    for bit in (0..width).rev() {
+        let mut last_split: Option<((usize, usize), usize)> = None;
+        for (rule, range) in rules.iter().zip(ranges.iter_mut()) {
+            let (start, end) = *range;
+            if end - start <= 1 {
+                continue;
+            }
+            let split = match last_split {
+                Some((last_range, split)) if last_range == *range => split,
+                _ => start + partition(&mut numbers[start..end], bit),
+            };
+            last_split = Some((*range, split));
+            *range = if rule.kept_bit(split - start, end - split) {
+                (split, end)
+            } else {
+                (start, split)
+            };
+        }
+    }
+    Some(ranges.map(|(start, _)| numbers[start]))
+}
@@ -0,0 +1,86 @@
+use crate::{
+    count_occurences, filter_rating_traced, BitCriteria, BitOrder, DiagnosticReport, FilterRule,
+    TieBreak,
+};
+use std::fmt::Write;
+
+const HEAT_SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Zeros and ones of every column over the whole report, the leftmost
+/// (most significant) column first.
+pub fn column_counts(report: &DiagnosticReport) -> Vec<(usize, usize)> {
+    (0..report.width)
+        .rev()
+        .map(|bit| count_occurences(&report.numbers, bit))
+        .collect()
+}
+
+pub fn column_counts_csv(report: &DiagnosticReport) -> String {
+    let mut csv = String::from("column,bit,zeros,ones\n");
+    for (column, (zero_occ, one_occ)) in column_counts(report).into_iter().enumerate() {
+        let bit = report.width - 1 - column;
+        writeln!(csv, "{},{},{},{}", column, bit, zero_occ, one_occ).unwrap();
+    }
+    csv
+}
+
+fn describe(rule: FilterRule) -> String {
+    let criteria = match rule.criteria {
+        BitCriteria::MostCommon => "most common",
+        BitCriteria::LeastCommon => "least common",
+    };
+    let tie = match rule.tie_break {
+        TieBreak::KeepOnes => 1,
+        TieBreak::KeepZeros => 0,
+    };
+    format!("{}, ties keep {}", criteria, tie)
+}
+
+/// Text report of the per column counts, shaded by their share of ones,
+/// followed by the rounds of filtering with each named rule.
+pub fn render_report(report: &DiagnosticReport, rules: &[(&str, FilterRule)]) -> String {
+    let mut text = String::new();
+    writeln!(text, "Column counts ({} numbers):", report.numbers.len()).unwrap();
+    writeln!(text, "column  bit  zeros   ones  share").unwrap();
+    for (column, (zero_occ, one_occ)) in column_counts(report).into_iter().enumerate() {
+        let shade = one_occ * (HEAT_SHADES.len() - 1) / report.numbers.len().max(1);
+        writeln!(
+            text,
+            "{:>6} {:>4} {:>6} {:>6}  {}",
+            column,
+            report.width - 1 - column,
+            zero_occ,
+            one_occ,
+            HEAT_SHADES[shade]
+        )
+        .unwrap();
+    }
+
+    for (name, rule) in rules {
+        let (rating, rounds) =
+            filter_rating_traced(&report.numbers, report.width, BitOrder::MsbFirst, *rule);
+        writeln!(text, "\n{} rounds ({}):", name, describe(*rule)).unwrap();
+        writeln!(text, " bit  zeros   ones  kept  remaining").unwrap();
+        for round in rounds {
+            writeln!(
+                text,
+                "{:>4} {:>6} {:>6} {:>5} {:>10}",
+                round.bit, round.zero_occ, round.one_occ, round.kept_bit as u8, round.remaining
+            )
+            .unwrap();
+        }
+        match rating {
+            Some(rating) => writeln!(
+                text,
+                "{} rating: {} ({:0width$b})",
+                name,
+                rating,
+                rating,
+                width = report.width
+            )
+            .unwrap(),
+            None => writeln!(text, "{} rating: none", name).unwrap(),
+        }
+    }
+    text
+}
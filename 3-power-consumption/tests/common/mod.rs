@@ -0,0 +1,12 @@
+/// Seeded xorshift generator shared by the randomized tests and benches, so
+/// runs are reproducible without pulling in a dependency.
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use common::XorShift;
+use power_consumption::partition::partitioned_ratings;
+use power_consumption::{
+    diagnose, filter_rating, BitCriteria, BitOrder, DiagnosticReport, FilterRule, ReportError,
+    TieBreak, CO2_SCRUBBER_RULE, OXYGEN_GENERATOR_RULE,
+};
+
+const EXAMPLE: [&str; 12] = [
+    "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+    "00010", "01010",
+];
+
+fn report(lines: &[&str]) -> Result<DiagnosticReport, ReportError> {
+    DiagnosticReport::new(lines.iter().map(|x| x.to_string()))
+}
+
+#[test]
+fn example_summary() {
+    let report = report(&EXAMPLE).unwrap();
+    assert_eq!(report.width, 5);
+    let summary = diagnose(&report);
+    assert_eq!(summary.gamma_rate, 22);
+    assert_eq!(summary.epsilon_rate, 9);
+    assert_eq!(summary.power_consumption, Some(198));
+    assert_eq!(summary.oxygen_generator_rating, 23);
+    assert_eq!(summary.co2_scrubber_rating, 10);
+    assert_eq!(summary.life_support_rating, Some(230));
+}
+
+#[test]
+fn bit_order_matters() {
+    let report = report(&EXAMPLE).unwrap();
+    let rating = |order, rule| filter_rating(&report.numbers, report.width, order, rule);
+    assert_eq!(
+        rating(BitOrder::MsbFirst, OXYGEN_GENERATOR_RULE),
+        Some(0b10111)
+    );
+    assert_eq!(rating(BitOrder::MsbFirst, CO2_SCRUBBER_RULE), Some(0b01010));
+    assert_eq!(
+        rating(BitOrder::LsbFirst, OXYGEN_GENERATOR_RULE),
+        Some(0b11110)
+    );
+    assert_eq!(rating(BitOrder::LsbFirst, CO2_SCRUBBER_RULE), Some(0b11001));
+}
+
+#[test]
+fn tie_break_policy() {
+    let numbers = [0b10, 0b01];
+    let rule = |criteria, tie_break| FilterRule {
+        criteria,
+        tie_break,
+    };
+    let rating = |rule| filter_rating(&numbers, 2, BitOrder::MsbFirst, rule);
+    assert_eq!(
+        rating(rule(BitCriteria::MostCommon, TieBreak::KeepOnes)),
+        Some(0b10)
+    );
+    assert_eq!(
+        rating(rule(BitCriteria::MostCommon, TieBreak::KeepZeros)),
+        Some(0b01)
+    );
+    assert_eq!(
+        rating(rule(BitCriteria::LeastCommon, TieBreak::KeepOnes)),
+        Some(0b10)
+    );
+}
+
+#[test]
+fn filtering_stops_at_one_candidate() {
+    // The least common first bit leaves a single number, whatever follows.
+    let numbers = [0b111, 0b110, 0b011];
+    assert_eq!(
+        filter_rating(&numbers, 3, BitOrder::MsbFirst, CO2_SCRUBBER_RULE),
+        Some(0b011)
+    );
+    assert_eq!(
+        filter_rating(&[], 3, BitOrder::MsbFirst, CO2_SCRUBBER_RULE),
+        None
+    );
+}
+
+#[test]
+fn duplicates_survive_every_bit() {
+    let numbers = [0b101, 0b101, 0b001];
+    assert_eq!(
+        filter_rating(&numbers, 3, BitOrder::MsbFirst, OXYGEN_GENERATOR_RULE),
+        Some(0b101)
+    );
+}
+
+#[test]
+fn invalid_reports() {
+    assert_eq!(report(&[]).err(), Some(ReportError::Empty));
+    assert_eq!(
+        report(&["0101", "011"]).err(),
+        Some(ReportError::MixedWidth {
+            line: 2,
+            expected: 4,
+            found: 3
+        })
+    );
+    assert_eq!(
+        report(&["0101", "0121"]).err(),
+        Some(ReportError::NonBinary {
+            line: 2,
+            column: 3,
+            found: '2'
+        })
+    );
+    let line = "1".repeat(129);
+    assert_eq!(
+        report(&[&line]).err(),
+        Some(ReportError::TooWide {
+            line: 1,
+            width: 129
+        })
+    );
+}
+
+#[test]
+fn wide_reports() {
+    let ones = "1".repeat(128);
+    let zeros = "0".repeat(128);
+    let report = report(&[&ones, &ones, &zeros]).unwrap();
+    let summary = diagnose(&report);
+    assert_eq!(summary.gamma_rate, u128::MAX);
+    assert_eq!(summary.epsilon_rate, 0);
+    assert_eq!(summary.oxygen_generator_rating, u128::MAX);
+    assert_eq!(summary.co2_scrubber_rating, 0);
+}
+
+#[test]
+fn partitioning_matches_filtering() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    let rules = [
+        OXYGEN_GENERATOR_RULE,
+        CO2_SCRUBBER_RULE,
+        FilterRule {
+            criteria: BitCriteria::MostCommon,
+            tie_break: TieBreak::KeepZeros,
+        },
+        FilterRule {
+            criteria: BitCriteria::LeastCommon,
+            tie_break: TieBreak::KeepOnes,
+        },
+    ];
+    for _ in 0..500 {
+        let width = 1 + (rng.next() % 10) as usize;
+        let len = 1 + (rng.next() % 50) as usize;
+        let lines: Vec<String> = (0..len)
+            .map(|_| {
+                let number = rng.next() % (1 << width);
+                format!("{:0width$b}", number, width = width)
+            })
+            .collect();
+        let report = DiagnosticReport::new(lines).unwrap();
+        let expected =
+            rules.map(|rule| filter_rating(&report.numbers, width, BitOrder::MsbFirst, rule));
+        let ratings = partitioned_ratings(&report.numbers, width, rules);
+        assert_eq!(
+            ratings.map(|x| x.map(Some)),
+            Some(expected),
+            "{:?}",
+            report.numbers
+        );
+    }
+}
+
+#[test]
+fn traced_rounds() {
+    let report = report(&EXAMPLE).unwrap();
+    let (rating, rounds) = power_consumption::filter_rating_traced(
+        &report.numbers,
+        report.width,
+        BitOrder::MsbFirst,
+        CO2_SCRUBBER_RULE,
+    );
+    assert_eq!(rating, Some(10));
+    let summary: Vec<(usize, usize, usize, bool, usize)> = rounds
+        .iter()
+        .map(|x| (x.bit, x.zero_occ, x.one_occ, x.kept_bit, x.remaining))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(4, 5, 7, false, 5), (3, 3, 2, true, 2), (2, 1, 1, false, 1)]
+    );
+    assert_eq!(
+        power_consumption::trace::column_counts(&report),
+        vec![(5, 7), (7, 5), (4, 8), (5, 7), (7, 5)]
+    );
+}
+
+#[test]
+fn column_counts_csv_export() {
+    let report = report(&EXAMPLE).unwrap();
+    assert_eq!(
+        power_consumption::trace::column_counts_csv(&report),
+        "column,bit,zeros,ones\n0,4,5,7\n1,3,7,5\n2,2,4,8\n3,1,5,7\n4,0,7,5\n"
+    );
+}
+
+#[test]
+fn rendered_co2_rounds() {
+    let report = report(&EXAMPLE).unwrap();
+    let text =
+        power_consumption::trace::render_report(&report, &[("CO2 scrubber", CO2_SCRUBBER_RULE)]);
+    assert_eq!(
+        text,
+        "Column counts (12 numbers):\n\
+         column  bit  zeros   ones  share\n\
+         \x20    0    4      5      7  ▒\n\
+         \x20    1    3      7      5  ░\n\
+         \x20    2    2      4      8  ▒\n\
+         \x20    3    1      5      7  ▒\n\
+         \x20    4    0      7      5  ░\n\
+         \n\
+         CO2 scrubber rounds (least common, ties keep 0):\n\
+         \x20bit  zeros   ones  kept  remaining\n\
+         \x20  4      5      7     0          5\n\
+         \x20  3      3      2     1          2\n\
+         \x20  2      1      1     0          1\n\
+         CO2 scrubber rating: 10 (01010)\n"
+    );
+}
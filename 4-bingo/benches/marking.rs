@@ -0,0 +1,69 @@
+//! Compares scanning every board on each called number with the indexed
+//! game on many generated boards. Run with `cargo bench`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use bingo::board::BingoBoard;
+use bingo::game::Game;
+use bingo::pattern::{win_lines, DEFAULT_PATTERNS};
+use bingo::simulation::Rng;
+use common::scanning_wins;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 5;
+const NUMBERS: u32 = 100;
+const BOARD_COUNTS: [usize; 3] = [1_000, 10_000, 50_000];
+const RUNS: u32 = 3;
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        f();
+    }
+    start.elapsed() / RUNS
+}
+
+fn shuffled(rng: &mut Rng) -> Vec<u32> {
+    let mut numbers: Vec<u32> = (0..NUMBERS).collect();
+    rng.shuffle(&mut numbers);
+    numbers
+}
+
+fn main() {
+    let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+    let lines = win_lines(&DEFAULT_PATTERNS, SIZE).unwrap();
+    let numbers = shuffled(&mut rng);
+    for count in BOARD_COUNTS {
+        let boards: Vec<Vec<String>> = (0..count)
+            .map(|_| {
+                shuffled(&mut rng)
+                    .chunks(SIZE)
+                    .take(SIZE)
+                    .map(|row| {
+                        row.iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    })
+                    .collect()
+            })
+            .collect();
+        let parse =
+            || -> Vec<BingoBoard> { boards.iter().map(|x| BingoBoard::new(x).unwrap()).collect() };
+
+        let scanning = time(|| {
+            black_box(scanning_wins(&mut parse(), &lines, &numbers));
+        });
+        let indexed = time(|| {
+            let mut game = Game::new(parse(), &DEFAULT_PATTERNS).unwrap();
+            game.play(numbers.iter().copied());
+            black_box(game.last_winner().copied());
+        });
+        println!(
+            "{:>6} boards: scanning {:>10.3?}, indexed {:>10.3?}",
+            count, scanning, indexed
+        );
+    }
+}
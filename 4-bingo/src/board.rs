@@ -0,0 +1,63 @@
+use crate::input::{parse_board, InputError, InputErrorReason};
+
+#[derive(Clone, Debug, Default)]
+pub struct BingoCell {
+    pub number: u32,
+    pub checked: bool,
+}
+
+/// Square board of `size` x `size` cells stored row by row.
+#[derive(Clone, Debug)]
+pub struct BingoBoard {
+    pub size: usize,
+    pub cells: Vec<BingoCell>,
+    pub has_won: bool,
+}
+
+impl BingoBoard {
+    /// Parses the rows of a single board with the same validation as
+    /// [`crate::input::parse_input`], error lines count from 1 at the first
+    /// row.
+    pub fn new(lines: &[String]) -> Result<BingoBoard, Vec<InputError>> {
+        if lines.is_empty() {
+            return Err(vec![InputError {
+                line: 1,
+                reason: InputErrorReason::Empty,
+            }]);
+        }
+        let rows: Vec<(usize, &str)> = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .collect();
+        parse_board(&rows, rows.len())
+    }
+
+    pub fn ugly_print(&self) {
+        let width = self
+            .cells
+            .iter()
+            .map(|x| x.number.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max(2);
+        for row in self.cells.chunks(self.size) {
+            for cell in row {
+                if cell.checked {
+                    print!("\x1b[93m{:0width$}\x1b[0m ", cell.number, width = width);
+                } else {
+                    print!("{:0width$} ", cell.number, width = width);
+                }
+            }
+            println!();
+        }
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.cells
+            .iter()
+            .filter(|y| !y.checked)
+            .map(|x| x.number)
+            .sum()
+    }
+}
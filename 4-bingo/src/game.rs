@@ -0,0 +1,223 @@
+use crate::board::BingoBoard;
+use crate::pattern::{win_lines, WinPattern};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameEvent {
+    NumberCalled {
+        number: u32,
+    },
+    CellMarked {
+        board: usize,
+        cell: usize,
+    },
+    BoardWon {
+        board: usize,
+        number: u32,
+        score: u64,
+    },
+}
+
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameEvent::NumberCalled { number } => write!(f, "called {}", number),
+            GameEvent::CellMarked { board, cell } => {
+                write!(f, "board {} marked cell {}", board, cell)
+            }
+            GameEvent::BoardWon {
+                board,
+                number,
+                score,
+            } => write!(f, "board {} won on {} with score {}", board, number, score),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Win {
+    pub board: usize,
+    /// 1-based count of numbers called up to and including the winning one.
+    pub draw: usize,
+    pub number: u32,
+    pub unmarked_sum: u32,
+    pub score: u64,
+}
+
+/// Plays called numbers against a set of boards. Boards stop being marked
+/// once they have won, and every step is recorded as an event.
+///
+/// Calling a number only visits the cells holding it: an index maps every
+/// number to its cells, and each board counts the checked cells of every
+/// win line, so a board has won as soon as one count reaches its line length.
+pub struct Game {
+    boards: Vec<BingoBoard>,
+    win_lines: Vec<Vec<usize>>,
+    /// Cells holding a number as (board, cell), ordered by board and cell.
+    number_index: HashMap<u32, Vec<(usize, usize)>>,
+    /// Indices into `win_lines` of the lines every cell belongs to.
+    cell_lines: Vec<Vec<usize>>,
+    /// Checked cells of every win line, per board.
+    line_counts: Vec<Vec<usize>>,
+    draws: usize,
+    events: Vec<GameEvent>,
+    wins: Vec<Win>,
+}
+
+impl Game {
+    /// Boards must all have the same size, which every pattern has to fit.
+    pub fn new(boards: Vec<BingoBoard>, patterns: &[WinPattern]) -> Result<Game, String> {
+        let size = boards.first().map_or(0, |x| x.size);
+        if boards.iter().any(|x| x.size != size) {
+            return Err("All boards must have the same size".to_string());
+        }
+        let win_lines = if boards.is_empty() {
+            Vec::new()
+        } else {
+            win_lines(patterns, size)?
+        };
+
+        let mut cell_lines = vec![Vec::new(); size * size];
+        for (line_index, line) in win_lines.iter().enumerate() {
+            for &cell in line {
+                cell_lines[cell].push(line_index);
+            }
+        }
+        let mut number_index: HashMap<u32, Vec<(usize, usize)>> = HashMap::new();
+        for (board_index, board) in boards.iter().enumerate() {
+            for (cell_index, cell) in board.cells.iter().enumerate() {
+                number_index
+                    .entry(cell.number)
+                    .or_default()
+                    .push((board_index, cell_index));
+            }
+        }
+        let line_counts = boards
+            .iter()
+            .map(|board| {
+                win_lines
+                    .iter()
+                    .map(|line| line.iter().filter(|&&x| board.cells[x].checked).count())
+                    .collect()
+            })
+            .collect();
+
+        Ok(Game {
+            boards,
+            win_lines,
+            number_index,
+            cell_lines,
+            line_counts,
+            draws: 0,
+            events: Vec::new(),
+            wins: Vec::new(),
+        })
+    }
+
+    /// Calls a number and returns the events it caused.
+    pub fn call(&mut self, number: u32) -> &[GameEvent] {
+        let first_event = self.events.len();
+        self.draws += 1;
+        self.events.push(GameEvent::NumberCalled { number });
+
+        let occurrences = self.number_index.get(&number).map_or(&[][..], |x| x);
+        let mut index = 0;
+        while index < occurrences.len() {
+            let board_index = occurrences[index].0;
+            let board = &mut self.boards[board_index];
+            let line_counts = &mut self.line_counts[board_index];
+            let mut completed_line = false;
+            while let Some(&(_, cell)) = occurrences.get(index).filter(|x| x.0 == board_index) {
+                index += 1;
+                if board.has_won || board.cells[cell].checked {
+                    continue;
+                }
+                board.cells[cell].checked = true;
+                self.events.push(GameEvent::CellMarked {
+                    board: board_index,
+                    cell,
+                });
+                for &line in &self.cell_lines[cell] {
+                    line_counts[line] += 1;
+                    completed_line |= line_counts[line] == self.win_lines[line].len();
+                }
+            }
+
+            if completed_line {
+                board.has_won = true;
+                let unmarked_sum = board.sum();
+                let score = unmarked_sum as u64 * number as u64;
+                self.events.push(GameEvent::BoardWon {
+                    board: board_index,
+                    number,
+                    score,
+                });
+                self.wins.push(Win {
+                    board: board_index,
+                    draw: self.draws,
+                    number,
+                    unmarked_sum,
+                    score,
+                });
+            }
+        }
+        &self.events[first_event..]
+    }
+
+    /// Unchecks every cell and forgets all calls, so the same boards can be
+    /// played again with other numbers.
+    pub fn reset(&mut self) {
+        for board in &mut self.boards {
+            board.has_won = false;
+            for cell in &mut board.cells {
+                cell.checked = false;
+            }
+        }
+        for counts in &mut self.line_counts {
+            counts.fill(0);
+        }
+        self.draws = 0;
+        self.events.clear();
+        self.wins.clear();
+    }
+
+    /// Calls numbers until every board has won or the numbers run out.
+    pub fn play<I>(&mut self, numbers: I)
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        for number in numbers {
+            if self.is_finished() {
+                break;
+            }
+            self.call(number);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.wins.len() == self.boards.len()
+    }
+
+    pub fn boards(&self) -> &[BingoBoard] {
+        &self.boards
+    }
+
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    /// Wins in the order they happened.
+    pub fn wins(&self) -> &[Win] {
+        &self.wins
+    }
+
+    pub fn first_winner(&self) -> Option<&Win> {
+        self.wins.first()
+    }
+
+    /// The board that won after all others, once every board has won.
+    pub fn last_winner(&self) -> Option<&Win> {
+        self.wins.last().filter(|_| self.is_finished())
+    }
+}
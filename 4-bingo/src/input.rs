@@ -0,0 +1,209 @@
+use crate::board::{BingoBoard, BingoCell};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum InputErrorReason {
+    Empty,
+    MissingBoards,
+    InvalidNumber(String),
+    RowWidth { expected: usize, found: usize },
+    NotSquare { rows: usize, columns: usize },
+    SizeMismatch { expected: usize, found: usize },
+    DuplicateNumber { number: u32, first_line: usize },
+}
+
+/// Input error pointing at a 1-based line.
+#[derive(Debug, PartialEq)]
+pub struct InputError {
+    pub line: usize,
+    pub reason: InputErrorReason,
+}
+
+impl fmt::Display for InputErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputErrorReason::Empty => write!(f, "input is empty"),
+            InputErrorReason::MissingBoards => write!(f, "no boards follow the called numbers"),
+            InputErrorReason::InvalidNumber(token) => {
+                write!(f, "invalid number '{}'", token)
+            }
+            InputErrorReason::RowWidth { expected, found } => {
+                write!(f, "row has {} numbers, expected {}", found, expected)
+            }
+            InputErrorReason::NotSquare { rows, columns } => {
+                write!(f, "board has {} rows of {} numbers", rows, columns)
+            }
+            InputErrorReason::SizeMismatch { expected, found } => write!(
+                f,
+                "board is {}x{} but the first board is {}x{}",
+                found, found, expected, expected
+            ),
+            InputErrorReason::DuplicateNumber { number, first_line } => write!(
+                f,
+                "number {} is already on this board at line {}",
+                number, first_line
+            ),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.line, self.reason)
+    }
+}
+
+impl Error for InputError {}
+
+#[derive(Debug)]
+pub struct BingoInput {
+    pub numbers: Vec<u32>,
+    pub boards: Vec<BingoBoard>,
+}
+
+fn parse_number(token: &str, line: usize) -> Result<u32, InputError> {
+    token.parse().map_err(|_| InputError {
+        line,
+        reason: InputErrorReason::InvalidNumber(token.to_string()),
+    })
+}
+
+/// Parses the comma separated called numbers followed by boards.
+///
+/// Boards are separated by any run of blank or whitespace only lines, and
+/// surrounding whitespace, `\r\n` line endings and a missing final newline
+/// are accepted. Every board has to be square, as large as the first one and
+/// must not hold a number twice. All bad lines are reported, not just the
+/// first one.
+pub fn parse_input(text: &str) -> Result<BingoInput, Vec<InputError>> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()));
+    let Some((numbers_line, numbers)) = lines.find(|x| !x.1.is_empty()) else {
+        return Err(vec![InputError {
+            line: 1,
+            reason: InputErrorReason::Empty,
+        }]);
+    };
+
+    let mut errors = Vec::new();
+    let numbers: Vec<u32> = numbers
+        .split(',')
+        .filter_map(|x| {
+            parse_number(x.trim(), numbers_line)
+                .map_err(|err| errors.push(err))
+                .ok()
+        })
+        .collect();
+
+    let mut chunks: Vec<Vec<(usize, &str)>> = Vec::new();
+    let mut previous_blank = true;
+    for (line, content) in lines {
+        if content.is_empty() {
+            previous_blank = true;
+            continue;
+        }
+        if previous_blank {
+            chunks.push(Vec::new());
+            previous_blank = false;
+        }
+        chunks.last_mut().unwrap().push((line, content));
+    }
+    if chunks.is_empty() {
+        errors.push(InputError {
+            line: numbers_line,
+            reason: InputErrorReason::MissingBoards,
+        });
+    }
+
+    let mut boards = Vec::new();
+    for chunk in &chunks {
+        // Sized by the row count of the first board, even if that one is bad.
+        match parse_board(chunk, chunks[0].len()) {
+            Ok(board) => boards.push(board),
+            Err(board_errors) => errors.extend(board_errors),
+        }
+    }
+
+    if !errors.is_empty() {
+        errors.sort_by_key(|x| x.line);
+        return Err(errors);
+    }
+    Ok(BingoInput { numbers, boards })
+}
+
+/// Parses the numbered, trimmed rows of one board that has to be `size`
+/// cells wide and high.
+pub(crate) fn parse_board(
+    rows: &[(usize, &str)],
+    size: usize,
+) -> Result<BingoBoard, Vec<InputError>> {
+    let mut errors = Vec::new();
+    let mut cells = Vec::new();
+    let mut seen: HashMap<u32, usize> = HashMap::new();
+    let first_line = rows[0].0;
+    let columns = rows[0].1.split_whitespace().count();
+
+    for &(line, content) in rows {
+        let tokens: Vec<&str> = content.split_whitespace().collect();
+        if tokens.len() != columns {
+            errors.push(InputError {
+                line,
+                reason: InputErrorReason::RowWidth {
+                    expected: columns,
+                    found: tokens.len(),
+                },
+            });
+        }
+        for token in tokens {
+            let number = match parse_number(token, line) {
+                Ok(number) => number,
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+            if let Some(&first_line) = seen.get(&number) {
+                errors.push(InputError {
+                    line,
+                    reason: InputErrorReason::DuplicateNumber { number, first_line },
+                });
+            }
+            seen.entry(number).or_insert(line);
+            cells.push(BingoCell {
+                number,
+                checked: false,
+            });
+        }
+    }
+
+    if columns != rows.len() {
+        errors.push(InputError {
+            line: first_line,
+            reason: InputErrorReason::NotSquare {
+                rows: rows.len(),
+                columns,
+            },
+        });
+    } else if columns != size {
+        errors.push(InputError {
+            line: first_line,
+            reason: InputErrorReason::SizeMismatch {
+                expected: size,
+                found: columns,
+            },
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(BingoBoard {
+        size: columns,
+        cells,
+        has_won: false,
+    })
+}
@@ -0,0 +1,5 @@
+pub mod board;
+pub mod game;
+pub mod input;
+pub mod pattern;
+pub mod simulation;
@@ -1,137 +1,156 @@
+use bingo::game::{Game, Win};
+use bingo::input::parse_input;
+use bingo::pattern::{WinPattern, DEFAULT_PATTERNS};
+use bingo::simulation::{simulate, SimulationConfig};
 use std::env;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::process;
 
-#[derive(Default)]
-struct BingoCell {
-    number: u32,
-    checked: bool,
+const USAGE: &str = "Usage: bingo [--events] [--win PATTERN,...] \
+                     [--simulate RUNS [--seed N] [--threads N]] FILE\n\
+                     Patterns: rows, columns, diagonals, corners, blackout, mask:ROW/ROW/...";
+
+struct Options {
+    show_events: bool,
+    patterns: Vec<WinPattern>,
+    simulation: Option<SimulationConfig>,
+    path: String,
 }
 
-struct BingoBoard {
-    cells: Vec<BingoCell>,
-    has_won: bool,
+fn parse_count(args: &mut std::slice::Iter<String>, flag: &str) -> Result<u64, String> {
+    let value = args
+        .next()
+        .ok_or_else(|| format!("Missing value for {}", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
 }
 
-impl BingoBoard {
-    fn new(lines: &[String]) -> BingoBoard {
-        assert!(lines.len() == 5);
-        let mut cells: Vec<BingoCell> = Vec::new();
-        for line in lines {
-            let numbers: Vec<u32> = line
-                .split_whitespace()
-                .map(|x| x.parse().unwrap())
-                .collect();
-            for number in numbers {
-                cells.push(BingoCell {
-                    number,
-                    checked: false,
-                });
-            }
-        }
-        BingoBoard {
-            cells,
-            has_won: false,
-        }
-    }
+impl Options {
+    fn new(args: &[String]) -> Result<Options, String> {
+        let mut show_events = false;
+        let mut patterns = DEFAULT_PATTERNS.to_vec();
+        let mut runs = None;
+        let mut seed = None;
+        let mut threads = None;
+        let mut path = None;
 
-    fn ugly_print(&self) {
-        for i in 0..self.cells.len() {
-            if self.cells[i].checked {
-                print!("\x1b[93m{:02}\x1b[0m ", self.cells[i].number);
-            } else {
-                print!("{:02} ", self.cells[i].number);
-            }
-            if (i as i32 - 4) % 5 == 0 {
-                print!("\n")
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--events" => show_events = true,
+                "--win" => {
+                    patterns =
+                        WinPattern::parse_list(args.next().ok_or("Missing value for --win")?)?
+                }
+                "--simulate" => runs = Some(parse_count(&mut args, arg)? as usize),
+                "--seed" => seed = Some(parse_count(&mut args, arg)?),
+                "--threads" => threads = Some(parse_count(&mut args, arg)? as usize),
+                _ if path.is_none() => path = Some(arg.clone()),
+                _ => return Err(format!("Unexpected argument: {}", arg)),
             }
         }
-    }
-
-    fn mark_number(&mut self, number: u32) {
-        for cell in self.cells.iter_mut() {
-            if cell.number == number {
-                cell.checked = true;
+        let simulation = match runs {
+            Some(0) => return Err("--simulate needs at least one run".to_string()),
+            Some(runs) => Some(SimulationConfig {
+                runs,
+                seed: seed.unwrap_or(0),
+                threads: threads
+                    .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |x| x.get())),
+            }),
+            None if threads.is_some() || seed.is_some() => {
+                return Err("--seed and --threads need --simulate".to_string())
             }
+            None => None,
+        };
+        if show_events && simulation.is_some() {
+            return Err("--events cannot be combined with --simulate".to_string());
         }
+        Ok(Options {
+            show_events,
+            patterns,
+            simulation,
+            path: path.ok_or("Provide the bingo input text file!")?,
+        })
     }
+}
 
-    fn has_bingo(&self) -> bool {
-        //check rows
-        for row_index in 0..5 {
-            let cells_checked = self.cells[row_index * 5..(row_index + 1) * 5]
-                .iter()
-                .filter(|x| x.checked)
-                .collect::<Vec<&BingoCell>>()
-                .len();
-            if cells_checked == 5 {
-                return true;
-            }
+fn print_winner(title: &str, game: &Game, win: &Win) {
+    println!("{} was board {} ....", title, win.board);
+    game.boards()[win.board].ugly_print();
+    println!("Sum of cells: {}", win.unmarked_sum);
+    println!("Called out number: {} (draw {})", win.number, win.draw);
+    println!("Score: {}", win.score);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = Options::new(&args).unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(1);
+    });
+
+    let text = fs::read_to_string(&options.path).expect("Error reading file");
+    let input = parse_input(&text).unwrap_or_else(|errors| {
+        for err in &errors {
+            eprintln!("{}:{}", options.path, err);
         }
+        eprintln!("{} input error(s)", errors.len());
+        process::exit(1);
+    });
+    let (called_out_numbers, boards) = (input.numbers, input.boards);
 
-        //check columns
-        'outer: for column_index in 0..5 {
-            for pos in (column_index..self.cells.len()).step_by(5) {
-                if !self.cells[pos].checked {
-                    continue 'outer;
-                }
+    if let Some(config) = options.simulation {
+        match simulate(&boards, &options.patterns, &called_out_numbers, config) {
+            Ok(simulation) => print!("{}", simulation),
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
             }
-            return true;
         }
-        false
-    }
-
-    fn sum(&self) -> u32 {
-        self.cells
-            .iter()
-            .filter(|y| !y.checked)
-            .map(|x| x.number)
-            .sum()
-    }
-}
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("Provide the bingo input text file!");
+        return;
     }
 
-    let file = File::open(&args[1]).expect("Error opening file");
-    let reader = BufReader::new(file);
-    let input_lines: Vec<String> = reader.lines().map(|x| x.unwrap()).collect();
+    let mut game = Game::new(boards, &options.patterns).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    game.play(called_out_numbers);
 
-    let called_out_numbers: Vec<u32> = input_lines[0]
-        .split(',')
-        .map(|x| x.parse().unwrap())
-        .collect();
-
-    let mut boards: Vec<BingoBoard> = Vec::new();
-    for i in (1..input_lines.len()).step_by(6) {
-        if i + 6 > input_lines.len() {
-            break;
+    if options.show_events {
+        for event in game.events() {
+            println!("{}", event);
         }
-        let board = BingoBoard::new(&input_lines[i + 1..i + 6]);
-        boards.push(board);
+        println!();
     }
 
-    let mut board_with_bingo = 0usize;
-    let board_count = boards.len();
-    'number_loop: for bingo_number in called_out_numbers {
-        for board in &mut boards {
-            if board.has_won {
-                continue;
-            }
-            board.mark_number(bingo_number);
-            if board.has_bingo() {
-                board.has_won = true;
-                if board_with_bingo == board_count - 1 {
-                    println!("And the last winner was ....");
-                    board.ugly_print();
-                    println!("Sum of cells: {}", board.sum());
-                    println!("Called out number: {}", bingo_number);
-                }
-                board_with_bingo += 1;
-            }
-        }
+    let Some(first) = game.first_winner() else {
+        println!("No board won");
+        return;
+    };
+    print_winner("The first winner", &game, first);
+    println!();
+    match game.last_winner() {
+        Some(last) => print_winner("And the last winner", &game, last),
+        None => println!("There is no last winner"),
+    }
+    println!();
+    println!("Win order:");
+    for (place, win) in game.wins().iter().enumerate() {
+        println!(
+            "{:>4}. board {:>3} on draw {:>3} (number {:>2}): score {}",
+            place + 1,
+            win.board,
+            win.draw,
+            win.number,
+            win.score
+        );
+    }
+    if !game.is_finished() {
+        println!(
+            "{} of {} boards never won",
+            game.boards().len() - game.wins().len(),
+            game.boards().len()
+        );
     }
 }
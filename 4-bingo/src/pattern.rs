@@ -0,0 +1,122 @@
+use std::fmt;
+
+/// Shape of cells that wins a board once all of them are checked.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WinPattern {
+    Rows,
+    Columns,
+    /// Both main diagonals.
+    Diagonals,
+    /// The four corner cells together.
+    Corners,
+    /// Every cell of the board.
+    Blackout,
+    /// Cells given row by row, `1` or `x` for cells that must be checked.
+    Custom(Vec<Vec<bool>>),
+}
+
+pub const DEFAULT_PATTERNS: [WinPattern; 2] = [WinPattern::Rows, WinPattern::Columns];
+
+impl WinPattern {
+    /// Parses a pattern name, or `mask:` followed by the rows of a custom
+    /// mask separated by `/`, e.g. `mask:101/010/101`.
+    pub fn new(text: &str) -> Result<WinPattern, String> {
+        match text {
+            "rows" => Ok(WinPattern::Rows),
+            "columns" => Ok(WinPattern::Columns),
+            "diagonals" => Ok(WinPattern::Diagonals),
+            "corners" => Ok(WinPattern::Corners),
+            "blackout" => Ok(WinPattern::Blackout),
+            _ => {
+                let mask = text
+                    .strip_prefix("mask:")
+                    .ok_or_else(|| format!("Unknown win pattern: {}", text))?;
+                let rows = mask
+                    .split('/')
+                    .map(|row| {
+                        row.chars()
+                            .map(|c| match c {
+                                '1' | 'x' => Ok(true),
+                                '0' | '.' => Ok(false),
+                                _ => Err(format!("Bad mask cell '{}' in {}", c, text)),
+                            })
+                            .collect()
+                    })
+                    .collect::<Result<Vec<Vec<bool>>, String>>()?;
+                if rows.iter().flatten().all(|x| !x) {
+                    return Err(format!("Mask {} selects no cell", text));
+                }
+                Ok(WinPattern::Custom(rows))
+            }
+        }
+    }
+
+    /// Comma separated list of patterns as accepted by [`WinPattern::new`].
+    pub fn parse_list(text: &str) -> Result<Vec<WinPattern>, String> {
+        text.split(',').map(|x| WinPattern::new(x.trim())).collect()
+    }
+
+    /// Cell index sets of this pattern on a `size` x `size` board, each of
+    /// which wins on its own.
+    pub fn lines(&self, size: usize) -> Result<Vec<Vec<usize>>, String> {
+        let lines = match self {
+            WinPattern::Rows => (0..size)
+                .map(|row| (row * size..(row + 1) * size).collect())
+                .collect(),
+            WinPattern::Columns => (0..size)
+                .map(|column| (column..size * size).step_by(size).collect())
+                .collect(),
+            WinPattern::Diagonals => vec![
+                (0..size).map(|i| i * size + i).collect(),
+                (0..size).map(|i| i * size + size - 1 - i).collect(),
+            ],
+            WinPattern::Corners => {
+                let mut corners = vec![0, size - 1, size * (size - 1), size * size - 1];
+                corners.dedup();
+                vec![corners]
+            }
+            WinPattern::Blackout => vec![(0..size * size).collect()],
+            WinPattern::Custom(rows) => {
+                if rows.len() != size || rows.iter().any(|x| x.len() != size) {
+                    return Err(format!("{} does not fit a {}x{} board", self, size, size));
+                }
+                vec![rows
+                    .iter()
+                    .flatten()
+                    .enumerate()
+                    .filter(|x| *x.1)
+                    .map(|x| x.0)
+                    .collect()]
+            }
+        };
+        Ok(lines)
+    }
+}
+
+impl fmt::Display for WinPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WinPattern::Rows => write!(f, "rows"),
+            WinPattern::Columns => write!(f, "columns"),
+            WinPattern::Diagonals => write!(f, "diagonals"),
+            WinPattern::Corners => write!(f, "corners"),
+            WinPattern::Blackout => write!(f, "blackout"),
+            WinPattern::Custom(rows) => {
+                let rows: Vec<String> = rows
+                    .iter()
+                    .map(|row| row.iter().map(|&x| if x { '1' } else { '0' }).collect())
+                    .collect();
+                write!(f, "mask:{}", rows.join("/"))
+            }
+        }
+    }
+}
+
+/// Every winning cell set of a pattern list on a board of the given size.
+pub fn win_lines(patterns: &[WinPattern], size: usize) -> Result<Vec<Vec<usize>>, String> {
+    let mut lines = Vec::new();
+    for pattern in patterns {
+        lines.extend(pattern.lines(size)?);
+    }
+    Ok(lines)
+}
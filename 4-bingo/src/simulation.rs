@@ -0,0 +1,234 @@
+use crate::board::BingoBoard;
+use crate::game::Game;
+use crate::pattern::WinPattern;
+use std::fmt;
+use std::thread;
+
+/// SplitMix64, small and good enough to shuffle draw sequences.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, (self.next_u64() % (index as u64 + 1)) as usize);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulationConfig {
+    pub runs: usize,
+    pub seed: u64,
+    pub threads: usize,
+}
+
+/// Outcome counts of one board over all runs.
+///
+/// Boards completing on the same draw share that place: such runs count as
+/// shared wins for every tied board instead of a win for any single one.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoardOdds {
+    pub first_wins: usize,
+    pub shared_first_wins: usize,
+    /// Only counted in runs in which every board won.
+    pub last_wins: usize,
+    pub shared_last_wins: usize,
+    /// Runs in which the board won at all.
+    pub wins: usize,
+    /// Draws needed to win, summed over the runs in which it won.
+    pub total_draws: u64,
+}
+
+impl BoardOdds {
+    fn add(&mut self, other: &BoardOdds) {
+        self.first_wins += other.first_wins;
+        self.shared_first_wins += other.shared_first_wins;
+        self.last_wins += other.last_wins;
+        self.shared_last_wins += other.shared_last_wins;
+        self.wins += other.wins;
+        self.total_draws += other.total_draws;
+    }
+
+    /// Mean number of draws to win over the runs in which the board won.
+    pub fn expected_draws(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.total_draws as f64 / self.wins as f64)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Simulation {
+    pub runs: usize,
+    /// Runs in which every board won, the only ones with a last winner.
+    pub finished_runs: usize,
+    pub boards: Vec<BoardOdds>,
+}
+
+impl Simulation {
+    pub fn first_win_probability(&self, board: usize) -> f64 {
+        self.boards[board].first_wins as f64 / self.runs as f64
+    }
+
+    pub fn shared_first_probability(&self, board: usize) -> f64 {
+        self.boards[board].shared_first_wins as f64 / self.runs as f64
+    }
+
+    pub fn last_win_probability(&self, board: usize) -> f64 {
+        self.boards[board].last_wins as f64 / self.runs as f64
+    }
+
+    pub fn shared_last_probability(&self, board: usize) -> f64 {
+        self.boards[board].shared_last_wins as f64 / self.runs as f64
+    }
+}
+
+impl fmt::Display for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Simulated {} shuffled draws, every board won in {}",
+            self.runs, self.finished_runs
+        )?;
+        writeln!(
+            f,
+            "Board  P(first) P(tie first)   P(last)  P(tie last)  Expected draws"
+        )?;
+        for (index, odds) in self.boards.iter().enumerate() {
+            let draws = odds
+                .expected_draws()
+                .map_or_else(|| "never won".to_string(), |x| format!("{:.2}", x));
+            writeln!(
+                f,
+                "{:>5} {:>9.4} {:>12.4} {:>9.4} {:>12.4} {:>15}",
+                index,
+                self.first_win_probability(index),
+                self.shared_first_probability(index),
+                self.last_win_probability(index),
+                self.shared_last_probability(index),
+                draws
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays the boards against `config.runs` shuffles of `numbers` and counts
+/// how often every board wins first and last, and after how many draws.
+///
+/// Runs are spread over `config.threads` threads. Every run seeds its own
+/// generator from `config.seed` and its index, so the result does not depend
+/// on the thread count.
+pub fn simulate(
+    boards: &[BingoBoard],
+    patterns: &[WinPattern],
+    numbers: &[u32],
+    config: SimulationConfig,
+) -> Result<Simulation, String> {
+    // Validates boards and patterns once before spawning any thread.
+    Game::new(boards.to_vec(), patterns)?;
+    let threads = config.threads.clamp(1, config.runs.max(1));
+
+    let partials: Vec<(usize, Vec<BoardOdds>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|thread_index| {
+                let runs = (thread_index * config.runs / threads)
+                    ..((thread_index + 1) * config.runs / threads);
+                scope.spawn(move || {
+                    let mut game = Game::new(boards.to_vec(), patterns).unwrap();
+                    let mut odds = vec![BoardOdds::default(); boards.len()];
+                    let mut finished_runs = 0;
+                    let mut draw_order = numbers.to_vec();
+                    for run in runs {
+                        draw_order.copy_from_slice(numbers);
+                        Rng::new(config.seed ^ Rng::new(run as u64).next_u64())
+                            .shuffle(&mut draw_order);
+                        game.reset();
+                        game.play(draw_order.iter().copied());
+                        record_run(&game, &mut odds);
+                        finished_runs += game.is_finished() as usize;
+                    }
+                    (finished_runs, odds)
+                })
+            })
+            .collect();
+        workers.into_iter().map(|x| x.join().unwrap()).collect()
+    });
+
+    let mut odds = vec![BoardOdds::default(); boards.len()];
+    let mut finished_runs = 0;
+    for (finished, partial) in &partials {
+        finished_runs += finished;
+        for (total, board) in odds.iter_mut().zip(partial) {
+            total.add(board);
+        }
+    }
+    Ok(Simulation {
+        runs: config.runs,
+        finished_runs,
+        boards: odds,
+    })
+}
+
+fn record_run(game: &Game, odds: &mut [BoardOdds]) {
+    let wins = game.wins();
+    for win in wins {
+        odds[win.board].wins += 1;
+        odds[win.board].total_draws += win.draw as u64;
+    }
+    if let Some(first) = game.first_winner() {
+        let tied: Vec<usize> = wins
+            .iter()
+            .take_while(|x| x.draw == first.draw)
+            .map(|x| x.board)
+            .collect();
+        credit_place(
+            odds,
+            &tied,
+            |x| &mut x.first_wins,
+            |x| &mut x.shared_first_wins,
+        );
+    }
+    if let Some(last) = game.last_winner() {
+        let tied: Vec<usize> = wins
+            .iter()
+            .rev()
+            .take_while(|x| x.draw == last.draw)
+            .map(|x| x.board)
+            .collect();
+        credit_place(
+            odds,
+            &tied,
+            |x| &mut x.last_wins,
+            |x| &mut x.shared_last_wins,
+        );
+    }
+}
+
+/// Counts a place for a single board, or as shared by every tied board.
+fn credit_place(
+    odds: &mut [BoardOdds],
+    boards: &[usize],
+    sole: fn(&mut BoardOdds) -> &mut usize,
+    shared: fn(&mut BoardOdds) -> &mut usize,
+) {
+    match boards {
+        [board] => *sole(&mut odds[*board]) += 1,
+        _ => {
+            for &board in boards {
+                *shared(&mut odds[board]) += 1;
+            }
+        }
+    }
+}
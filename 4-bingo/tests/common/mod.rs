@@ -0,0 +1,32 @@
+use bingo::board::BingoBoard;
+
+/// Plays the boards by scanning every cell of every board on each call, the
+/// way the game worked before it indexed numbers. Returns the board, draw
+/// and score of every win in order.
+pub fn scanning_wins(
+    boards: &mut [BingoBoard],
+    lines: &[Vec<usize>],
+    numbers: &[u32],
+) -> Vec<(usize, usize, u64)> {
+    let mut wins = Vec::new();
+    for (draw, &number) in numbers.iter().enumerate() {
+        for (index, board) in boards.iter_mut().enumerate() {
+            if board.has_won {
+                continue;
+            }
+            for cell in board.cells.iter_mut() {
+                if cell.number == number {
+                    cell.checked = true;
+                }
+            }
+            if lines
+                .iter()
+                .any(|line| line.iter().all(|&pos| board.cells[pos].checked))
+            {
+                board.has_won = true;
+                wins.push((index, draw + 1, board.sum() as u64 * number as u64));
+            }
+        }
+    }
+    wins
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use bingo::board::BingoBoard;
+use bingo::game::{Game, GameEvent};
+use bingo::pattern::{win_lines, WinPattern, DEFAULT_PATTERNS};
+use bingo::simulation::Rng;
+use common::scanning_wins;
+
+const EXAMPLE_NUMBERS: [u32; 27] = [
+    7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8, 19, 3, 26,
+    1,
+];
+
+const EXAMPLE_BOARDS: [[&str; 5]; 3] = [
+    [
+        "22 13 17 11  0",
+        " 8  2 23  4 24",
+        "21  9 14 16  7",
+        " 6 10  3 18  5",
+        " 1 12 20 15 19",
+    ],
+    [
+        " 3 15  0  2 22",
+        " 9 18 13 17  5",
+        "19  8  7 25 23",
+        "20 11 10 24  4",
+        "14 21 16 12  6",
+    ],
+    [
+        "14 21 17 24  4",
+        "10 16 15  9 19",
+        "18  8 23 26 20",
+        "22 11 13  6  5",
+        " 2  0 12  3  7",
+    ],
+];
+
+fn board(rows: &[&str]) -> BingoBoard {
+    let lines: Vec<String> = rows.iter().map(|x| x.to_string()).collect();
+    BingoBoard::new(&lines).unwrap()
+}
+
+fn example_game(patterns: &[WinPattern]) -> Game {
+    let boards = EXAMPLE_BOARDS.iter().map(|x| board(x)).collect();
+    let mut game = Game::new(boards, patterns).unwrap();
+    game.play(EXAMPLE_NUMBERS);
+    game
+}
+
+#[test]
+fn example_first_and_last_winner() {
+    let game = example_game(&DEFAULT_PATTERNS);
+    let first = game.first_winner().unwrap();
+    assert_eq!((first.board, first.number, first.score), (2, 24, 4512));
+    let last = game.last_winner().unwrap();
+    assert_eq!((last.board, last.number, last.score), (1, 13, 1924));
+}
+
+#[test]
+fn no_last_winner_until_every_board_won() {
+    let boards = vec![board(&["1 2", "3 4"]), board(&["5 6", "7 8"])];
+    let mut game = Game::new(boards, &DEFAULT_PATTERNS).unwrap();
+    game.play([1, 2, 9]);
+    assert_eq!(game.first_winner().unwrap().board, 0);
+    assert!(game.last_winner().is_none());
+
+    game.play([5, 6]);
+    assert_eq!(game.last_winner().unwrap().board, 1);
+}
+
+#[test]
+fn events_follow_every_call() {
+    let boards = vec![board(&["1 2", "3 4"]), board(&["2 5", "6 7"])];
+    let mut game = Game::new(boards, &DEFAULT_PATTERNS).unwrap();
+
+    assert_eq!(
+        game.call(1),
+        &[
+            GameEvent::NumberCalled { number: 1 },
+            GameEvent::CellMarked { board: 0, cell: 0 },
+        ]
+    );
+    assert_eq!(
+        game.call(2),
+        &[
+            GameEvent::NumberCalled { number: 2 },
+            GameEvent::CellMarked { board: 0, cell: 1 },
+            GameEvent::BoardWon {
+                board: 0,
+                number: 2,
+                score: 14,
+            },
+            GameEvent::CellMarked { board: 1, cell: 0 },
+        ]
+    );
+    // Board 0 has already won, so it no longer marks its cells.
+    assert_eq!(game.call(3), &[GameEvent::NumberCalled { number: 3 }]);
+    assert_eq!(
+        game.call(5),
+        &[
+            GameEvent::NumberCalled { number: 5 },
+            GameEvent::CellMarked { board: 1, cell: 1 },
+            GameEvent::BoardWon {
+                board: 1,
+                number: 5,
+                score: 65,
+            },
+        ]
+    );
+    assert!(game.is_finished());
+    assert_eq!(game.events().len(), 10);
+    assert_eq!(
+        game.events()[4].to_string(),
+        "board 0 won on 2 with score 14"
+    );
+    assert_eq!(
+        game.wins().iter().map(|x| x.draw).collect::<Vec<usize>>(),
+        vec![2, 4]
+    );
+}
+
+#[test]
+fn parse_pattern_list() {
+    let patterns = WinPattern::parse_list("rows, diagonals,mask:x.x/.x./x.x").unwrap();
+    assert_eq!(patterns.len(), 3);
+    assert_eq!(patterns[0], WinPattern::Rows);
+    assert_eq!(patterns[1], WinPattern::Diagonals);
+    assert_eq!(patterns[2].to_string(), "mask:101/010/101");
+
+    assert!(WinPattern::new("stars").is_err());
+    assert!(WinPattern::new("mask:1a1").is_err());
+    assert!(WinPattern::new("mask:00/00").is_err());
+}
+
+#[test]
+fn pattern_lines() {
+    assert_eq!(
+        WinPattern::Diagonals.lines(3).unwrap(),
+        vec![vec![0, 4, 8], vec![2, 4, 6]]
+    );
+    assert_eq!(
+        WinPattern::Corners.lines(3).unwrap(),
+        vec![vec![0, 2, 6, 8]]
+    );
+    assert_eq!(WinPattern::Corners.lines(1).unwrap(), vec![vec![0]]);
+    assert_eq!(
+        WinPattern::Columns.lines(2).unwrap(),
+        vec![vec![0, 2], vec![1, 3]]
+    );
+    assert_eq!(
+        win_lines(&[WinPattern::Rows, WinPattern::Blackout], 2).unwrap(),
+        vec![vec![0, 1], vec![2, 3], vec![0, 1, 2, 3]]
+    );
+
+    let mask = WinPattern::new("mask:01/10").unwrap();
+    assert_eq!(mask.lines(2).unwrap(), vec![vec![1, 2]]);
+    assert!(mask.lines(3).is_err());
+}
+
+#[test]
+fn diagonal_and_corner_wins() {
+    let rows = ["1 2 3", "4 5 6", "7 8 9"];
+
+    let mut game = Game::new(vec![board(&rows)], &[WinPattern::Diagonals]).unwrap();
+    game.play([3, 5, 1, 9]);
+    assert_eq!(game.first_winner().unwrap().number, 9);
+
+    let mut game = Game::new(vec![board(&rows)], &[WinPattern::Corners]).unwrap();
+    game.play([1, 2, 3, 5, 7, 9]);
+    let win = game.first_winner().unwrap();
+    assert_eq!((win.draw, win.unmarked_sum), (6, 4 + 6 + 8));
+}
+
+#[test]
+fn blackout_needs_every_cell() {
+    let game = example_game(&[WinPattern::Blackout]);
+    assert!(game.is_finished());
+    assert!(game.wins().iter().all(|x| x.score == 0 && x.draw > 20));
+
+    let mut game = Game::new(vec![board(&["1 2", "3 4"])], &[WinPattern::Blackout]).unwrap();
+    game.play([4, 3, 2, 1]);
+    let win = game.first_winner().unwrap();
+    assert_eq!((win.draw, win.score), (4, 0));
+}
+
+#[test]
+fn mismatched_sizes_are_rejected() {
+    let boards = vec![board(&["1 2", "3 4"]), board(&["1"])];
+    assert!(Game::new(boards, &DEFAULT_PATTERNS).is_err());
+
+    let mask = WinPattern::new("mask:111/000/000").unwrap();
+    assert!(Game::new(vec![board(&["1 2", "3 4"])], &[mask]).is_err());
+}
+
+#[test]
+fn indexed_marking_matches_scanning() {
+    let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+    let patterns = [WinPattern::Rows, WinPattern::Columns, WinPattern::Diagonals];
+    for size in 1..6 {
+        // Small number ranges so boards share most of their numbers.
+        let range = (size * size * 2) as u64;
+        let boards: Vec<Vec<String>> = (0..40)
+            .map(|_| {
+                let mut numbers: Vec<u64> = (0..range).collect();
+                rng.shuffle(&mut numbers);
+                numbers[..size * size]
+                    .chunks(size)
+                    .map(|row| {
+                        row.iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    })
+                    .collect()
+            })
+            .collect();
+        let numbers: Vec<u32> = (0..range * 2)
+            .map(|_| (rng.next_u64() % range) as u32)
+            .collect();
+        let parse =
+            || -> Vec<BingoBoard> { boards.iter().map(|x| BingoBoard::new(x).unwrap()).collect() };
+
+        let mut game = Game::new(parse(), &patterns).unwrap();
+        game.play(numbers.iter().copied());
+        let indexed: Vec<(usize, usize, u64)> = game
+            .wins()
+            .iter()
+            .map(|x| (x.board, x.draw, x.score))
+            .collect();
+
+        let lines = win_lines(&patterns, size).unwrap();
+        let expected = scanning_wins(&mut parse(), &lines, &numbers);
+        assert_eq!(indexed, expected, "size {}", size);
+    }
+}
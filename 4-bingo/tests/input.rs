@@ -0,0 +1,113 @@
+use bingo::board::BingoBoard;
+use bingo::input::{parse_input, InputError, InputErrorReason};
+
+#[test]
+fn irregular_blank_lines_and_whitespace() {
+    let text = "\n7, 4,9 \r\n\n\n 1  2 \r\n3 4\n  \n\t\n\n\n4 3\n2 1   ";
+    let input = parse_input(text).unwrap();
+    assert_eq!(input.numbers, vec![7, 4, 9]);
+    assert_eq!(input.boards.len(), 2);
+    let cells: Vec<u32> = input.boards[1].cells.iter().map(|x| x.number).collect();
+    assert_eq!(cells, vec![4, 3, 2, 1]);
+    assert!(input.boards.iter().all(|x| x.size == 2));
+}
+
+#[test]
+fn missing_final_newline_keeps_the_last_row() {
+    let with_newline = parse_input("1,2\n\n1 2\n3 4\n").unwrap();
+    let without = parse_input("1,2\n\n1 2\n3 4").unwrap();
+    assert_eq!(with_newline.boards[0].cells.len(), 4);
+    assert_eq!(without.boards[0].cells.len(), 4);
+}
+
+#[test]
+fn errors_carry_line_numbers() {
+    let text = "1,x,3\n\n1 2 3\n4 5\n7 8 9\n\n1 2\n2 4\n\n1 2\n3 4\n5 6";
+    let errors = parse_input(text).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            InputError {
+                line: 1,
+                reason: InputErrorReason::InvalidNumber("x".to_string()),
+            },
+            InputError {
+                line: 4,
+                reason: InputErrorReason::RowWidth {
+                    expected: 3,
+                    found: 2,
+                },
+            },
+            InputError {
+                line: 7,
+                reason: InputErrorReason::SizeMismatch {
+                    expected: 3,
+                    found: 2,
+                },
+            },
+            InputError {
+                line: 8,
+                reason: InputErrorReason::DuplicateNumber {
+                    number: 2,
+                    first_line: 7,
+                },
+            },
+            InputError {
+                line: 10,
+                reason: InputErrorReason::NotSquare {
+                    rows: 3,
+                    columns: 2,
+                },
+            },
+        ]
+    );
+    assert_eq!(
+        errors[3].to_string(),
+        "8: number 2 is already on this board at line 7"
+    );
+}
+
+#[test]
+fn empty_inputs() {
+    let errors = parse_input(" \n\n").unwrap_err();
+    assert_eq!(errors[0].reason, InputErrorReason::Empty);
+    let errors = parse_input("1,2,3\n\n").unwrap_err();
+    assert_eq!(errors[0].reason, InputErrorReason::MissingBoards);
+}
+
+#[test]
+fn single_boards_are_validated_like_inputs() {
+    let rows = |rows: &[&str]| -> Vec<String> { rows.iter().map(|x| x.to_string()).collect() };
+    let board = BingoBoard::new(&rows(&[" 1 2 ", "3 4"])).unwrap();
+    assert_eq!(board.size, 2);
+
+    let errors = BingoBoard::new(&rows(&["1 2", "2 x"])).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            InputError {
+                line: 2,
+                reason: InputErrorReason::DuplicateNumber {
+                    number: 2,
+                    first_line: 1,
+                },
+            },
+            InputError {
+                line: 2,
+                reason: InputErrorReason::InvalidNumber("x".to_string()),
+            },
+        ]
+    );
+    let errors = BingoBoard::new(&rows(&["1 2 3", "4 5 6"])).unwrap_err();
+    assert_eq!(
+        errors[0].reason,
+        InputErrorReason::NotSquare {
+            rows: 2,
+            columns: 3,
+        }
+    );
+    assert_eq!(
+        BingoBoard::new(&[]).unwrap_err()[0].reason,
+        InputErrorReason::Empty
+    );
+}
@@ -0,0 +1,114 @@
+use bingo::board::BingoBoard;
+use bingo::pattern::{WinPattern, DEFAULT_PATTERNS};
+use bingo::simulation::{simulate, Rng, SimulationConfig};
+
+fn boards() -> Vec<BingoBoard> {
+    [["1 2", "3 4"], ["4 5", "6 7"], ["1 5", "8 3"]]
+        .iter()
+        .map(|rows| {
+            let lines: Vec<String> = rows.iter().map(|x| x.to_string()).collect();
+            BingoBoard::new(&lines).unwrap()
+        })
+        .collect()
+}
+
+fn config(runs: usize, threads: usize) -> SimulationConfig {
+    SimulationConfig {
+        runs,
+        seed: 42,
+        threads,
+    }
+}
+
+#[test]
+fn shuffle_is_a_seeded_permutation() {
+    let mut numbers: Vec<u32> = (0..50).collect();
+    Rng::new(1).shuffle(&mut numbers);
+    let mut again: Vec<u32> = (0..50).collect();
+    Rng::new(1).shuffle(&mut again);
+    assert_eq!(numbers, again);
+    assert_ne!(numbers, (0..50).collect::<Vec<u32>>());
+
+    numbers.sort_unstable();
+    assert_eq!(numbers, (0..50).collect::<Vec<u32>>());
+}
+
+#[test]
+fn result_does_not_depend_on_thread_count() {
+    let numbers: Vec<u32> = (1..=8).collect();
+    let single = simulate(&boards(), &DEFAULT_PATTERNS, &numbers, config(500, 1)).unwrap();
+    for threads in [2, 3, 16, 1000] {
+        let parallel =
+            simulate(&boards(), &DEFAULT_PATTERNS, &numbers, config(500, threads)).unwrap();
+        assert_eq!(single, parallel, "{} threads", threads);
+    }
+}
+
+#[test]
+fn probabilities_add_up() {
+    let numbers: Vec<u32> = (1..=8).collect();
+    let simulation = simulate(&boards(), &DEFAULT_PATTERNS, &numbers, config(2000, 4)).unwrap();
+    assert_eq!((simulation.runs, simulation.finished_runs), (2000, 2000));
+    // Every run has a first and a last place, held alone or shared.
+    let sole_first: usize = simulation.boards.iter().map(|x| x.first_wins).sum();
+    let sole_last: usize = simulation.boards.iter().map(|x| x.last_wins).sum();
+    let shared_first: usize = simulation.boards.iter().map(|x| x.shared_first_wins).sum();
+    let shared_last: usize = simulation.boards.iter().map(|x| x.shared_last_wins).sum();
+    assert!(sole_first < 2000 && sole_first + shared_first / 3 <= 2000);
+    assert!(sole_first + shared_first / 2 >= 2000);
+    assert!(sole_last < 2000 && sole_last + shared_last / 3 <= 2000);
+    assert!(sole_last + shared_last / 2 >= 2000);
+
+    // Every board wins in every run, after at least two and at most all draws.
+    for odds in &simulation.boards {
+        assert_eq!(odds.wins, 2000);
+        let draws = odds.expected_draws().unwrap();
+        assert!((2.0..=8.0).contains(&draws));
+        // Each board has a fair share of first and last wins.
+        assert!(odds.first_wins > 300 && odds.last_wins > 300);
+    }
+}
+
+#[test]
+fn boards_that_cannot_win() {
+    let numbers = [1, 2, 3, 4, 9];
+    let simulation =
+        simulate(&boards(), &[WinPattern::Blackout], &numbers, config(100, 2)).unwrap();
+    // Only the first board can be completed, so it always wins first and no
+    // run has a last winner.
+    assert_eq!(simulation.finished_runs, 0);
+    assert_eq!(simulation.first_win_probability(0), 1.0);
+    assert!(simulation.boards[0].expected_draws().unwrap() >= 4.0);
+    for board in 1..3 {
+        assert_eq!(simulation.first_win_probability(board), 0.0);
+        assert_eq!(simulation.boards[board].expected_draws(), None);
+    }
+    for odds in &simulation.boards {
+        assert_eq!((odds.last_wins, odds.shared_last_wins), (0, 0));
+    }
+}
+
+#[test]
+fn ties_are_shared() {
+    let boards: Vec<BingoBoard> = [["1 2", "3 4"], ["4 3", "2 1"]]
+        .iter()
+        .map(|rows| {
+            let lines: Vec<String> = rows.iter().map(|x| x.to_string()).collect();
+            BingoBoard::new(&lines).unwrap()
+        })
+        .collect();
+    let simulation = simulate(
+        &boards,
+        &[WinPattern::Blackout],
+        &[1, 2, 3, 4],
+        config(50, 2),
+    )
+    .unwrap();
+    assert_eq!(simulation.finished_runs, 50);
+    for board in 0..2 {
+        assert_eq!(simulation.first_win_probability(board), 0.0);
+        assert_eq!(simulation.shared_first_probability(board), 1.0);
+        assert_eq!(simulation.last_win_probability(board), 0.0);
+        assert_eq!(simulation.shared_last_probability(board), 1.0);
+    }
+}
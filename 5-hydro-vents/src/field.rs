@@ -0,0 +1,174 @@
+use crate::{Bounds, LineFilter, VentPath, LINE_FILTERS};
+use std::collections::HashMap;
+
+/// Bounding box area up to which a field keeps one counter per cell. Larger
+/// inputs are counted sparsely, so far apart vents cost memory only for the
+/// cells they cover.
+pub const DENSE_CELL_LIMIT: u64 = 1 << 22;
+
+/// Largest bounding box area dense storage is allocated for even when it is
+/// asked for explicitly, 1 GiB of counters.
+pub const MAX_DENSE_CELLS: u64 = 1 << 28;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Storage {
+    Auto,
+    /// One counter per cell of the bounding box, up to [`MAX_DENSE_CELLS`].
+    Dense,
+    Sparse,
+}
+
+impl Storage {
+    pub fn new(text: &str) -> Result<Storage, &'static str> {
+        match text {
+            "auto" => Ok(Storage::Auto),
+            "dense" => Ok(Storage::Dense),
+            "sparse" => Ok(Storage::Sparse),
+            _ => Err("Unknown field storage"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Cells {
+    /// Row by row counters of the cells inside `bounds`.
+    Dense {
+        bounds: Bounds,
+        counts: Vec<u32>,
+    },
+    Sparse(HashMap<(u32, u32), u32>),
+}
+
+fn dense_index(bounds: &Bounds, x: u32, y: u32) -> Option<usize> {
+    bounds
+        .contains(x, y)
+        .then(|| ((y - bounds.min.y) as u64 * bounds.width() + (x - bounds.min.x) as u64) as usize)
+}
+
+/// Number of vents crossing every cell.
+#[derive(Debug)]
+pub struct VentField {
+    cells: Cells,
+}
+
+impl VentField {
+    /// Field able to hold every path. Dense storage is sized from the
+    /// bounding box of the paths, `Storage::Auto` only picks it up to
+    /// [`DENSE_CELL_LIMIT`] cells and `Storage::Dense` fails beyond
+    /// [`MAX_DENSE_CELLS`].
+    pub fn new(paths: &[VentPath], storage: Storage) -> Result<VentField, &'static str> {
+        let bounds = Bounds::new(paths);
+        let dense = match storage {
+            Storage::Auto => bounds.is_some_and(|x| x.area() <= DENSE_CELL_LIMIT),
+            Storage::Dense if bounds.is_some_and(|x| x.area() > MAX_DENSE_CELLS) => {
+                return Err("Bounding box is too large for dense storage");
+            }
+            Storage::Dense => bounds.is_some(),
+            Storage::Sparse => false,
+        };
+        let cells = match bounds {
+            Some(bounds) if dense => Cells::Dense {
+                bounds,
+                counts: vec![0; bounds.area() as usize],
+            },
+            _ => Cells::Sparse(HashMap::new()),
+        };
+        Ok(VentField { cells })
+    }
+
+    pub fn is_dense(&self) -> bool {
+        matches!(self.cells, Cells::Dense { .. })
+    }
+
+    /// Marks a single cell. A dense field moves its counters to sparse
+    /// storage before marking outside of its bounds.
+    pub fn mark_single_field(&mut self, x: u32, y: u32) {
+        if let Cells::Dense { bounds, counts } = &mut self.cells {
+            if let Some(index) = dense_index(bounds, x, y) {
+                counts[index] += 1;
+                return;
+            }
+            self.cells = Cells::Sparse(self.marked_cells().collect());
+        }
+        if let Cells::Sparse(counts) = &mut self.cells {
+            *counts.entry((x, y)).or_insert(0) += 1;
+        }
+    }
+
+    pub fn count(&self, x: u32, y: u32) -> u32 {
+        match &self.cells {
+            Cells::Dense { bounds, counts } => dense_index(bounds, x, y).map_or(0, |x| counts[x]),
+            Cells::Sparse(counts) => counts.get(&(x, y)).copied().unwrap_or(0),
+        }
+    }
+
+    /// Every cell crossed by at least one vent with its count.
+    fn marked_cells(&self) -> Box<dyn Iterator<Item = ((u32, u32), u32)> + '_> {
+        match &self.cells {
+            Cells::Dense { bounds, counts } => {
+                let width = bounds.width();
+                Box::new(counts.iter().enumerate().filter(|x| *x.1 > 0).map(
+                    move |(index, &count)| {
+                        let x = bounds.min.x + (index as u64 % width) as u32;
+                        let y = bounds.min.y + (index as u64 / width) as u32;
+                        ((x, y), count)
+                    },
+                ))
+            }
+            Cells::Sparse(counts) => Box::new(counts.iter().map(|(&point, &count)| (point, count))),
+        }
+    }
+
+    /// Marks every cell of the path if `filter` accepts its kind and
+    /// returns whether it did. Lines of any slope are rasterized, see
+    /// [`crate::raster::LinePoints`].
+    pub fn mark_path(&mut self, path: &VentPath, filter: LineFilter) -> bool {
+        if !filter.accepts(path.kind()) {
+            return false;
+        }
+        for point in path.points() {
+            self.mark_single_field(point.x, point.y);
+        }
+        true
+    }
+
+    pub fn get_dangerous_field_count(&self) -> usize {
+        self.marked_cells().filter(|x| x.1 >= 2).count()
+    }
+
+    /// Prints the counts inside `bounds`, `.` for cells without a vent.
+    pub fn print(&self, bounds: Bounds) {
+        for y in bounds.min.y..=bounds.max.y {
+            let row: String = (bounds.min.x..=bounds.max.x)
+                .map(|x| match self.count(x, y) {
+                    0 => '.',
+                    count => char::from_digit(count.min(9), 10).unwrap(),
+                })
+                .collect();
+            println!("{}", row);
+        }
+    }
+}
+
+/// Dangerous cell count under every [`LineFilter`]. As each filter extends
+/// the one before it, the paths are marked on a single field, adding the
+/// lines of one filter after the other.
+pub fn dangerous_counts(
+    paths: &[VentPath],
+    storage: Storage,
+) -> Result<Vec<(LineFilter, usize)>, &'static str> {
+    let mut field = VentField::new(paths, storage)?;
+    let mut counts = Vec::new();
+    let mut previous: Option<LineFilter> = None;
+    for filter in LINE_FILTERS {
+        for path in paths {
+            let kind = path.kind();
+            if filter.accepts(kind) && !previous.is_some_and(|x| x.accepts(kind)) {
+                field.mark_path(path, filter);
+            }
+        }
+        counts.push((filter, field.get_dangerous_field_count()));
+        previous = Some(filter);
+    }
+    Ok(counts)
+}
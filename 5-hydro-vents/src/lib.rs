@@ -0,0 +1,156 @@
+pub mod field;
+pub mod raster;
+
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Point {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Point {
+    fn new(text: &str) -> Result<Point, &'static str> {
+        let coordinates = text
+            .split(',')
+            .map(|x| x.trim().parse())
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| "Bad coordinate")?;
+        match coordinates[..] {
+            [x, y] => Ok(Point { x, y }),
+            _ => Err("Param count"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct VentPath {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl VentPath {
+    pub fn new(text: &str) -> Result<VentPath, &'static str> {
+        let coordinates: Vec<&str> = text.split("->").map(|x| x.trim()).collect();
+        if coordinates.len() != 2 {
+            return Err("Param count");
+        }
+        Ok(VentPath {
+            start: Point::new(coordinates[0])?,
+            end: Point::new(coordinates[1])?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineKind {
+    Horizontal,
+    Vertical,
+    /// 45 degree diagonal, a single point also counts as one.
+    Diagonal,
+    /// Any other slope.
+    Other,
+}
+
+impl VentPath {
+    pub fn kind(&self) -> LineKind {
+        let x_distance = self.start.x.abs_diff(self.end.x);
+        let y_distance = self.start.y.abs_diff(self.end.y);
+        if x_distance == y_distance {
+            LineKind::Diagonal
+        } else if x_distance == 0 {
+            LineKind::Vertical
+        } else if y_distance == 0 {
+            LineKind::Horizontal
+        } else {
+            LineKind::Other
+        }
+    }
+}
+
+/// Which vent lines are marked on a field. Every filter includes the lines
+/// of the ones before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineFilter {
+    AxisAligned,
+    WithDiagonals,
+    All,
+}
+
+pub const LINE_FILTERS: [LineFilter; 3] = [
+    LineFilter::AxisAligned,
+    LineFilter::WithDiagonals,
+    LineFilter::All,
+];
+
+impl LineFilter {
+    pub fn new(text: &str) -> Result<LineFilter, &'static str> {
+        match text {
+            "axis" => Ok(LineFilter::AxisAligned),
+            "diagonal" => Ok(LineFilter::WithDiagonals),
+            "all" => Ok(LineFilter::All),
+            _ => Err("Unknown line filter"),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineFilter::AxisAligned => "axis",
+            LineFilter::WithDiagonals => "diagonal",
+            LineFilter::All => "all",
+        }
+    }
+
+    pub fn accepts(&self, kind: LineKind) -> bool {
+        match self {
+            LineFilter::AxisAligned => matches!(kind, LineKind::Horizontal | LineKind::Vertical),
+            LineFilter::WithDiagonals => kind != LineKind::Other,
+            LineFilter::All => true,
+        }
+    }
+}
+
+/// Smallest rectangle holding a set of points, `min` and `max` inclusive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// Bounds of all path end points, `None` without any path.
+    pub fn new(paths: &[VentPath]) -> Option<Bounds> {
+        let mut points = paths.iter().flat_map(|x| [x.start, x.end]);
+        let first = points.next()?;
+        Some(points.fold(
+            Bounds {
+                min: first,
+                max: first,
+            },
+            |bounds, point| Bounds {
+                min: Point {
+                    x: bounds.min.x.min(point.x),
+                    y: bounds.min.y.min(point.y),
+                },
+                max: Point {
+                    x: bounds.max.x.max(point.x),
+                    y: bounds.max.y.max(point.y),
+                },
+            },
+        ))
+    }
+
+    pub fn width(&self) -> u64 {
+        (self.max.x - self.min.x) as u64 + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        (self.max.y - self.min.y) as u64 + 1
+    }
+
+    pub fn area(&self) -> u64 {
+        self.width().saturating_mul(self.height())
+    }
+
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        (self.min.x..=self.max.x).contains(&x) && (self.min.y..=self.max.y).contains(&y)
+    }
+}
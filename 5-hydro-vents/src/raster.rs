@@ -0,0 +1,80 @@
+use crate::{LineKind, Point, VentPath};
+
+/// Cells covered by a vent path from its start to its end point.
+///
+/// Axis-aligned and 45 degree lines are walked one unit step at a time.
+/// Other slopes fall back to Bresenham's algorithm, which picks the cell
+/// closest to the ideal line in every step along the longer axis. Either
+/// way a path yields one cell per step along its longer axis, plus one.
+pub struct LinePoints {
+    x: i64,
+    y: i64,
+    step_x: i64,
+    step_y: i64,
+    /// Bresenham error terms `(dx, -dy, error)`, `None` for unit steps.
+    bresenham: Option<(i64, i64, i64)>,
+    remaining: u64,
+}
+
+impl LinePoints {
+    pub fn new(path: &VentPath) -> LinePoints {
+        let (x0, y0) = (path.start.x as i64, path.start.y as i64);
+        let (x1, y1) = (path.end.x as i64, path.end.y as i64);
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        LinePoints {
+            x: x0,
+            y: y0,
+            step_x: (x1 - x0).signum(),
+            step_y: (y1 - y0).signum(),
+            bresenham: (path.kind() == LineKind::Other).then_some((dx, dy, dx + dy)),
+            remaining: dx.max(-dy) as u64 + 1,
+        }
+    }
+}
+
+impl Iterator for LinePoints {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let point = Point {
+            x: self.x as u32,
+            y: self.y as u32,
+        };
+        if self.remaining > 0 {
+            match &mut self.bresenham {
+                None => {
+                    self.x += self.step_x;
+                    self.y += self.step_y;
+                }
+                Some((dx, dy, error)) => {
+                    let doubled = 2 * *error;
+                    if doubled >= *dy {
+                        *error += *dy;
+                        self.x += self.step_x;
+                    }
+                    if doubled <= *dx {
+                        *error += *dx;
+                        self.y += self.step_y;
+                    }
+                }
+            }
+        }
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::try_from(self.remaining).ok();
+        (remaining.unwrap_or(usize::MAX), remaining)
+    }
+}
+
+impl VentPath {
+    pub fn points(&self) -> LinePoints {
+        LinePoints::new(self)
+    }
+}
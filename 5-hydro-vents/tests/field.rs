@@ -0,0 +1,170 @@
+use hydro_vents::field::{dangerous_counts, Storage, VentField, DENSE_CELL_LIMIT, MAX_DENSE_CELLS};
+use hydro_vents::{Bounds, LineFilter, LineKind, Point, VentPath};
+
+const EXAMPLE: [&str; 10] = [
+    "0,9 -> 5,9",
+    "8,0 -> 0,8",
+    "9,4 -> 3,4",
+    "2,2 -> 2,1",
+    "7,0 -> 7,4",
+    "6,4 -> 2,0",
+    "0,9 -> 2,9",
+    "3,4 -> 1,4",
+    "0,0 -> 8,8",
+    "5,5 -> 8,2",
+];
+
+fn paths(lines: &[&str]) -> Vec<VentPath> {
+    lines.iter().map(|x| VentPath::new(x).unwrap()).collect()
+}
+
+fn dangerous(paths: &[VentPath], storage: Storage) -> usize {
+    filtered_dangerous(paths, storage, LineFilter::All)
+}
+
+fn filtered_dangerous(paths: &[VentPath], storage: Storage, filter: LineFilter) -> usize {
+    let mut field = VentField::new(paths, storage).unwrap();
+    for path in paths {
+        field.mark_path(path, filter);
+    }
+    field.get_dangerous_field_count()
+}
+
+#[test]
+fn example_in_every_storage() {
+    let paths = paths(&EXAMPLE);
+    for storage in [Storage::Auto, Storage::Dense, Storage::Sparse] {
+        assert_eq!(dangerous(&paths, storage), 12, "{:?}", storage);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        VentPath::new(" 1,2 ->3, 4 ").unwrap(),
+        VentPath {
+            start: Point { x: 1, y: 2 },
+            end: Point { x: 3, y: 4 },
+        }
+    );
+    assert_eq!(VentPath::new("1,2 -> 3"), Err("Param count"));
+    assert_eq!(VentPath::new("1,2"), Err("Param count"));
+    assert_eq!(VentPath::new("1,-2 -> 3,4"), Err("Bad coordinate"));
+    assert_eq!(VentPath::new("1,2 -> 3,99999999999"), Err("Bad coordinate"));
+}
+
+#[test]
+fn field_is_sized_from_the_bounding_box() {
+    let paths = paths(&["1500,2000 -> 1500,2010", "1490,2005 -> 1510,2005"]);
+    let bounds = Bounds::new(&paths).unwrap();
+    assert_eq!((bounds.width(), bounds.height()), (21, 11));
+
+    let field = VentField::new(&paths, Storage::Auto).unwrap();
+    assert!(field.is_dense());
+    assert_eq!(dangerous(&paths, Storage::Auto), 1);
+}
+
+#[test]
+fn huge_coordinates_are_counted_sparsely() {
+    let paths = paths(&[
+        "0,0 -> 4,0",
+        "2,0 -> 2,3",
+        "4000000000,4000000000 -> 4000000000,3999999990",
+        "4000000000,3999999995 -> 4000000000,3999999999",
+        "4294967295,4294967295 -> 4294967292,4294967292",
+        "4294967292,4294967295 -> 4294967295,4294967292",
+    ]);
+    assert!(Bounds::new(&paths).unwrap().area() > DENSE_CELL_LIMIT);
+    assert!(!VentField::new(&paths, Storage::Auto).unwrap().is_dense());
+    assert_eq!(dangerous(&paths, Storage::Auto), 1 + 5);
+}
+
+#[test]
+fn marking_outside_a_dense_field() {
+    let paths = paths(&["0,0 -> 3,0"]);
+    let mut field = VentField::new(&paths, Storage::Dense).unwrap();
+    field.mark_path(&paths[0], LineFilter::All);
+    field.mark_single_field(1, 0);
+    field.mark_single_field(100, 100);
+    field.mark_single_field(100, 100);
+    assert!(!field.is_dense());
+    assert_eq!(field.count(1, 0), 2);
+    assert_eq!(field.count(100, 100), 2);
+    assert_eq!(field.get_dangerous_field_count(), 2);
+}
+
+#[test]
+fn empty_field() {
+    let field = VentField::new(&[], Storage::Dense).unwrap();
+    assert_eq!(field.get_dangerous_field_count(), 0);
+    assert_eq!(field.count(0, 0), 0);
+}
+
+#[test]
+fn line_kinds_and_filters() {
+    let kinds: Vec<LineKind> = paths(&[
+        "1,1 -> 1,3",
+        "9,7 -> 7,7",
+        "1,1 -> 3,3",
+        "4,4 -> 4,4",
+        "0,0 -> 2,1",
+    ])
+    .iter()
+    .map(|x| x.kind())
+    .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            LineKind::Vertical,
+            LineKind::Horizontal,
+            LineKind::Diagonal,
+            LineKind::Diagonal,
+            LineKind::Other
+        ]
+    );
+    assert!(LineFilter::AxisAligned.accepts(LineKind::Vertical));
+    assert!(!LineFilter::AxisAligned.accepts(LineKind::Diagonal));
+    assert!(LineFilter::WithDiagonals.accepts(LineKind::Diagonal));
+    assert!(!LineFilter::WithDiagonals.accepts(LineKind::Other));
+    assert!(LineFilter::All.accepts(LineKind::Other));
+
+    let mut field = VentField::new(&[], Storage::Auto).unwrap();
+    assert!(!field.mark_path(
+        &VentPath::new("0,0 -> 2,2").unwrap(),
+        LineFilter::AxisAligned
+    ));
+    assert_eq!(field.count(1, 1), 0);
+}
+
+#[test]
+fn example_per_filter() {
+    let paths = paths(&EXAMPLE);
+    assert_eq!(
+        filtered_dangerous(&paths, Storage::Auto, LineFilter::AxisAligned),
+        5
+    );
+    assert_eq!(
+        filtered_dangerous(&paths, Storage::Sparse, LineFilter::WithDiagonals),
+        12
+    );
+    assert_eq!(
+        dangerous_counts(&paths, Storage::Auto).unwrap(),
+        vec![
+            (LineFilter::AxisAligned, 5),
+            (LineFilter::WithDiagonals, 12),
+            (LineFilter::All, 12)
+        ]
+    );
+}
+
+#[test]
+fn dense_storage_refuses_huge_bounds() {
+    let huge = paths(&["0,0 -> 4294967295,4294967295"]);
+    assert!(Bounds::new(&huge).unwrap().area() > MAX_DENSE_CELLS);
+    assert!(VentField::new(&huge, Storage::Dense).is_err());
+    assert!(dangerous_counts(&huge, Storage::Dense).is_err());
+
+    let small = paths(&["0,0 -> 99,0", "5,0 -> 5,9"]);
+    let field = VentField::new(&small, Storage::Dense).unwrap();
+    assert!(field.is_dense());
+}
@@ -0,0 +1,104 @@
+use hydro_vents::field::{Storage, VentField};
+use hydro_vents::{LineFilter, Point, VentPath};
+
+fn points(line: &str) -> Vec<(u32, u32)> {
+    VentPath::new(line)
+        .unwrap()
+        .points()
+        .map(|p| (p.x, p.y))
+        .collect()
+}
+
+#[test]
+fn axis_and_diagonal_lines_step_by_one() {
+    assert_eq!(points("1,1 -> 1,3"), vec![(1, 1), (1, 2), (1, 3)]);
+    assert_eq!(points("9,7 -> 7,7"), vec![(9, 7), (8, 7), (7, 7)]);
+    assert_eq!(points("9,7 -> 7,9"), vec![(9, 7), (8, 8), (7, 9)]);
+    assert_eq!(points("0,2 -> 2,0"), vec![(0, 2), (1, 1), (2, 0)]);
+    assert_eq!(points("4,4 -> 4,4"), vec![(4, 4)]);
+}
+
+#[test]
+fn bresenham_for_other_slopes() {
+    assert_eq!(
+        points("0,0 -> 4,2"),
+        vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+    );
+    assert_eq!(points("0,0 -> 1,3"), vec![(0, 0), (0, 1), (1, 2), (1, 3)]);
+    assert_eq!(points("5,1 -> 0,3").len(), 6);
+
+    // Every step moves to a neighbouring cell and the line ends on its end
+    // point, whichever octant it points into.
+    for line in [
+        "0,0 -> 7,3",
+        "7,3 -> 0,0",
+        "3,0 -> 0,7",
+        "0,7 -> 3,0",
+        "2,9 -> 11,1",
+    ] {
+        let path = VentPath::new(line).unwrap();
+        let cells: Vec<Point> = path.points().collect();
+        assert_eq!(cells.first(), Some(&path.start), "{}", line);
+        assert_eq!(cells.last(), Some(&path.end), "{}", line);
+        let longer = path
+            .start
+            .x
+            .abs_diff(path.end.x)
+            .max(path.start.y.abs_diff(path.end.y));
+        assert_eq!(cells.len() as u32, longer + 1, "{}", line);
+        for pair in cells.windows(2) {
+            assert!(pair[0].x.abs_diff(pair[1].x) <= 1 && pair[0].y.abs_diff(pair[1].y) <= 1);
+        }
+    }
+}
+
+#[test]
+fn extreme_coordinates() {
+    let line = "4294967295,0 -> 0,4294967295";
+    let path = VentPath::new(line).unwrap();
+    assert_eq!(path.points().size_hint(), (1 << 32, Some(1 << 32)));
+    assert_eq!(
+        points("4294967295,4294967292 -> 4294967293,4294967295"),
+        vec![
+            (4294967295, 4294967292),
+            (4294967294, 4294967293),
+            (4294967294, 4294967294),
+            (4294967293, 4294967295)
+        ]
+    );
+}
+
+#[test]
+fn long_diagonals_are_linear() {
+    let paths = vec![
+        VentPath::new("0,0 -> 200000,200000").unwrap(),
+        VentPath::new("200000,0 -> 0,200000").unwrap(),
+        VentPath::new("0,100000 -> 200000,100000").unwrap(),
+    ];
+    let mut field = VentField::new(&paths, Storage::Sparse).unwrap();
+    for path in &paths {
+        field.mark_path(path, LineFilter::WithDiagonals);
+    }
+    assert_eq!(field.count(100000, 100000), 3);
+    assert_eq!(field.get_dangerous_field_count(), 1);
+}
+
+#[test]
+fn all_filter_marks_arbitrary_slopes() {
+    let paths: Vec<VentPath> = ["0,0 -> 4,2", "2,0 -> 2,4", "0,4 -> 4,0"]
+        .iter()
+        .map(|x| VentPath::new(x).unwrap())
+        .collect();
+    let mut field = VentField::new(&paths, Storage::Auto).unwrap();
+    let marked: Vec<bool> = paths
+        .iter()
+        .map(|x| field.mark_path(x, LineFilter::WithDiagonals))
+        .collect();
+    assert_eq!(marked, vec![false, true, true]);
+    assert_eq!(field.get_dangerous_field_count(), 1);
+
+    assert!(field.mark_path(&paths[0], LineFilter::All));
+    assert_eq!(field.count(2, 1), 2);
+    assert_eq!(field.count(2, 2), 2);
+    assert_eq!(field.get_dangerous_field_count(), 2);
+}